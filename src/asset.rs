@@ -0,0 +1,94 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::availability::{availabilities_of, Availabilities};
+use crate::diff::attributes_of;
+use crate::error::{MMCError, MMCResult};
+
+/// The countries an asset may be played in
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct GeoProfile {
+    /// The name of the profile, such as `Domestic`
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The ISO 3166 alpha-2 codes of the countries the asset may be played in. An empty list
+    /// places no restriction on where the asset may be played.
+    #[serde(default)]
+    pub countries: Vec<String>,
+}
+
+impl GeoProfile {
+    /// Returns true when the profile lists no countries or the supplied country code is one of
+    /// them, ignoring case
+    pub fn allows(&self, country: &str) -> bool {
+        self.countries.is_empty()
+            || self
+                .countries
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(country))
+    }
+}
+
+/// The attributes of an asset needed to reason about it without further API calls
+#[derive(Clone, Debug, PartialEq)]
+pub struct Asset {
+    /// The id of the asset
+    pub id: String,
+
+    /// The title of the asset, if it was present
+    pub title: Option<String>,
+
+    /// The kind of asset, such as `full_length` or `clip`, if it was present
+    pub object_type: Option<String>,
+
+    /// True when the asset's `publish_state` marks it as published. Assets without a publish
+    /// state are treated as unpublished.
+    pub published: bool,
+
+    /// The windows during which the asset is available to each audience
+    pub availabilities: Availabilities,
+
+    /// The countries the asset is restricted to, or `None` when it is not restricted
+    pub geo_profile: Option<GeoProfile>,
+}
+
+impl Asset {
+    /// Reads an asset from a full API document (`{"data": {...}}`) or a resource object as it
+    /// appears in a list
+    pub fn from_value(value: &Value) -> MMCResult<Asset> {
+        let resource = value.get("data").unwrap_or(value);
+        let attributes = attributes_of(resource).ok();
+        let attribute = |name: &str| attributes.and_then(|attributes| attributes.get(name));
+        let string = |name: &str| attribute(name).and_then(Value::as_str).map(String::from);
+
+        let published = match attribute("publish_state") {
+            Some(Value::Number(state)) => state.as_i64() == Some(1),
+            Some(Value::String(state)) => state == "published",
+            _ => false,
+        };
+
+        let geo_profile = match attribute("geo_profile") {
+            None | Some(Value::Null) => None,
+            Some(profile) => {
+                Some(serde_json::from_value(profile.clone()).map_err(MMCError::Parse)?)
+            }
+        };
+
+        Ok(Asset {
+            id: resource
+                .get("id")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            title: string("title"),
+            object_type: string("object_type"),
+            published,
+            availabilities: match attributes {
+                Some(_) => availabilities_of(resource)?,
+                None => Availabilities::new(),
+            },
+            geo_profile,
+        })
+    }
+}
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::error::{MMCError, MMCResult};
+
+/// Describes a single create, update, delete or move performed by a [Client](struct.Client.html)
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AuditRecord {
+    /// When the response was received, or when the request failed
+    pub timestamp: DateTime<Utc>,
+
+    /// The operator label the client was configured with
+    pub operator: String,
+
+    /// The HTTP method of the request
+    pub method: String,
+
+    /// The fully formed url of the request
+    pub url: String,
+
+    /// The JSON body of the request, if it had one
+    pub body: Option<Value>,
+
+    /// The HTTP status of the response, or `None` if no response was received
+    pub status: Option<u16>,
+
+    /// The id of the object that was changed, when it can be determined from the response or the
+    /// request body
+    pub id: Option<String>,
+}
+
+/// A destination for the audit records generated by a [Client](struct.Client.html)
+pub trait AuditSink: Send + Sync {
+    /// Called once for every mutating request sent by the client, whether or not it succeeded
+    fn record(&self, record: &AuditRecord);
+}
+
+impl fmt::Debug for dyn AuditSink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AuditSink")
+    }
+}
+
+/// An [AuditSink](trait.AuditSink.html) that writes each record as a line of JSON
+///
+/// Records are flushed as they are written. Failures to write a record do not fail the request
+/// that was audited.
+pub struct JsonLinesAudit {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl JsonLinesAudit {
+    /// Creates a sink that writes records to the supplied writer
+    pub fn new<W: Write + Send + 'static>(writer: W) -> JsonLinesAudit {
+        JsonLinesAudit {
+            writer: Mutex::new(Box::new(writer)),
+        }
+    }
+
+    /// Creates a sink that appends records to the file at the supplied path, creating it if it
+    /// does not exist
+    pub fn open<P: AsRef<Path>>(path: P) -> MMCResult<JsonLinesAudit> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map(JsonLinesAudit::new)
+            .map_err(MMCError::Io)
+    }
+}
+
+impl fmt::Debug for JsonLinesAudit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "JsonLinesAudit")
+    }
+}
+
+impl AuditSink for JsonLinesAudit {
+    fn record(&self, record: &AuditRecord) {
+        if let Ok(line) = serde_json::to_string(record) {
+            let mut writer = self
+                .writer
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            let _ = writeln!(writer, "{}", line).and_then(|_| writer.flush());
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use std::collections::BTreeMap;
+
+use crate::client::{Client, Endpoints};
+use crate::diff::attributes_of;
+use crate::error::{MMCError, MMCResult};
+
+/// The availability windows of an asset, keyed by the audience they apply to. Audiences without
+/// a window can not view the asset.
+pub type Availabilities = BTreeMap<Audience, AvailabilityWindow>;
+
+/// The audiences that an asset can be made available to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Audience {
+    /// Every viewer
+    Public,
+
+    /// Members of any station
+    AllMembers,
+
+    /// Members of the station that owns the content
+    StationMembers,
+}
+
+impl Audience {
+    /// Returns every audience, from the widest to the narrowest
+    pub fn all() -> Vec<Audience> {
+        vec![
+            Audience::Public,
+            Audience::AllMembers,
+            Audience::StationMembers,
+        ]
+    }
+
+    /// Returns the key the audience is stored under in an asset's `availabilities`
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Audience::Public => "public",
+            Audience::AllMembers => "all_members",
+            Audience::StationMembers => "station_members",
+        }
+    }
+}
+
+/// The period during which an asset is available to an [Audience](enum.Audience.html)
+///
+/// A window without a start is available immediately, and a window without an end remains
+/// available indefinitely.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AvailabilityWindow {
+    /// When the asset becomes available
+    #[serde(default)]
+    pub start: Option<DateTime<Utc>>,
+
+    /// When the asset stops being available
+    #[serde(default)]
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl AvailabilityWindow {
+    /// Creates a window between the supplied instants
+    pub fn new(start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> AvailabilityWindow {
+        AvailabilityWindow { start, end }
+    }
+
+    /// Checks that the window starts before it ends
+    pub fn validate(&self, audience: Audience) -> MMCResult<()> {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) if start >= end => Err(MMCError::InvalidWindow(format!(
+                "The {} window starts at {} which is not before its end at {}",
+                audience.as_str(),
+                start.to_rfc3339(),
+                end.to_rfc3339()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the window moved later by the supplied duration, or earlier if it is negative.
+    /// Unset bounds remain unset. Fails when a bound would be moved beyond the dates that can be
+    /// represented.
+    pub fn shifted(&self, audience: Audience, by: Duration) -> MMCResult<AvailabilityWindow> {
+        Ok(AvailabilityWindow {
+            start: self
+                .start
+                .map(|start| moved(start, audience, by))
+                .transpose()?,
+            end: self.end.map(|end| moved(end, audience, by)).transpose()?,
+        })
+    }
+
+    /// Returns the window with its end moved later by the supplied duration. Fails for windows
+    /// without an end, as they already remain available indefinitely, and when the end would be
+    /// moved beyond the dates that can be represented.
+    pub fn extended(&self, audience: Audience, by: Duration) -> MMCResult<AvailabilityWindow> {
+        match self.end {
+            Some(end) => Ok(AvailabilityWindow {
+                start: self.start,
+                end: Some(moved(end, audience, by)?),
+            }),
+            None => Err(MMCError::InvalidWindow(format!(
+                "The {} window has no end to extend",
+                audience.as_str()
+            ))),
+        }
+    }
+
+    /// Returns the window ending at the supplied instant
+    pub fn expired(&self, at: DateTime<Utc>) -> AvailabilityWindow {
+        AvailabilityWindow {
+            start: self.start,
+            end: Some(at),
+        }
+    }
+
+    /// Returns true when the supplied instant falls within the window. Windows include their
+    /// start and exclude their end.
+    pub fn contains(&self, instant: DateTime<Utc>) -> bool {
+        let started = match self.start {
+            Some(start) => start <= instant,
+            None => true,
+        };
+
+        let ended = match self.end {
+            Some(end) => end <= instant,
+            None => false,
+        };
+
+        started && !ended
+    }
+}
+
+/// Reads the availability windows from an asset in any of the forms accepted by
+/// [attributes_of](fn.attributes_of.html). Audiences whose window is `null` are omitted.
+pub fn availabilities_of(asset: &Value) -> MMCResult<Availabilities> {
+    let windows = match attributes_of(asset)?.get("availabilities") {
+        Some(Value::Object(windows)) => windows,
+        _ => return Ok(Availabilities::new()),
+    };
+
+    Audience::all()
+        .into_iter()
+        .filter_map(|audience| match windows.get(audience.as_str()) {
+            None | Some(Value::Null) => None,
+            Some(window) => Some(
+                serde_json::from_value(window.clone())
+                    .map(|window| (audience, window))
+                    .map_err(MMCError::Parse),
+            ),
+        })
+        .collect()
+}
+
+impl Client {
+    /// Attempts to fetch the edit object of an asset and read its availability windows
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = asset_id)))]
+    pub fn availabilities(&self, asset_id: &str) -> MMCResult<Availabilities> {
+        let body = self.edit(Endpoints::Asset, asset_id)?;
+        let parsed: Value = serde_json::from_str(body.as_str()).map_err(MMCError::Parse)?;
+
+        availabilities_of(&parsed)
+    }
+
+    /// Replaces the window of an asset for a single audience, leaving the windows of other
+    /// audiences unchanged
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = asset_id, audience = audience.as_str()))
+    )]
+    pub fn set_availability(
+        &self,
+        asset_id: &str,
+        audience: Audience,
+        window: AvailabilityWindow,
+    ) -> MMCResult<String> {
+        self.change_availabilities(asset_id, |windows| {
+            windows.insert(audience, window);
+            Ok(())
+        })
+    }
+
+    /// Moves the end of an asset's window for a single audience later by the supplied duration
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = asset_id, audience = audience.as_str()))
+    )]
+    pub fn extend_availability(
+        &self,
+        asset_id: &str,
+        audience: Audience,
+        by: Duration,
+    ) -> MMCResult<String> {
+        self.change_availabilities(asset_id, |windows| {
+            let window = windows.get(&audience).ok_or_else(|| missing(audience))?;
+            let extended = window.extended(audience, by)?;
+            windows.insert(audience, extended);
+            Ok(())
+        })
+    }
+
+    /// Ends an asset's window for a single audience at the supplied instant
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = asset_id, audience = audience.as_str()))
+    )]
+    pub fn expire_availability(
+        &self,
+        asset_id: &str,
+        audience: Audience,
+        at: DateTime<Utc>,
+    ) -> MMCResult<String> {
+        self.change_availabilities(asset_id, |windows| {
+            let window = windows.get(&audience).ok_or_else(|| missing(audience))?;
+            let expired = window.expired(at);
+            windows.insert(audience, expired);
+            Ok(())
+        })
+    }
+
+    /// Moves every window of an asset later by the supplied duration, or earlier if it is
+    /// negative
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = asset_id)))]
+    pub fn shift_availability(&self, asset_id: &str, by: Duration) -> MMCResult<String> {
+        self.change_availabilities(asset_id, |windows| {
+            for (audience, window) in windows.iter_mut() {
+                *window = window.shifted(*audience, by)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Moves every window of every asset beneath a season or show by the supplied duration, as
+    /// found by [crawl_assets](struct.Client.html#method.crawl_assets). Every asset is attempted
+    /// even when others fail, and the result of its update is returned for each asset id in the
+    /// order they were found.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(parent = %parent, parent_id = parent_id))
+    )]
+    pub fn shift_availability_beneath(
+        &self,
+        parent: Endpoints,
+        parent_id: &str,
+        by: Duration,
+    ) -> MMCResult<Vec<(String, MMCResult<String>)>> {
+        match parent {
+            Endpoints::Season | Endpoints::Show => (),
+            other => {
+                return Err(MMCError::InvalidWindow(format!(
+                    "Windows can only be shifted beneath a season or show, not a {}",
+                    other.singular()
+                )))
+            }
+        }
+
+        let results = self
+            .crawl_assets(parent, parent_id)?
+            .iter()
+            .filter_map(|crawled| crawled.asset.get("id").and_then(Value::as_str))
+            .map(|id| (id.to_string(), self.shift_availability(id, by)))
+            .collect();
+
+        Ok(results)
+    }
+
+    // Reads the windows of an asset, applies a change to them, checks that the changed windows
+    // are valid and sends them back as a single update. Windows left as they were are not checked,
+    // so that a window the server already holds can not block changes to other audiences.
+    fn change_availabilities<F>(&self, asset_id: &str, change: F) -> MMCResult<String>
+    where
+        F: FnOnce(&mut Availabilities) -> MMCResult<()>,
+    {
+        let current = self.availabilities(asset_id)?;
+        let mut windows = current.clone();
+        change(&mut windows)?;
+
+        for (audience, window) in windows.iter() {
+            if current.get(audience) != Some(window) {
+                window.validate(*audience)?;
+            }
+        }
+
+        let body = serde_json::json!({
+            "data": {
+                "type": Endpoints::Asset.singular(),
+                "id": asset_id,
+                "attributes": {"availabilities": windows},
+            }
+        });
+
+        self.update(Endpoints::Asset, asset_id, &body)
+    }
+}
+
+fn moved(instant: DateTime<Utc>, audience: Audience, by: Duration) -> MMCResult<DateTime<Utc>> {
+    instant.checked_add_signed(by).ok_or_else(|| {
+        MMCError::InvalidWindow(format!(
+            "The {} window can not be moved from {} by {}",
+            audience.as_str(),
+            instant.to_rfc3339(),
+            by
+        ))
+    })
+}
+
+fn missing(audience: Audience) -> MMCError {
+    MMCError::InvalidWindow(format!("The asset has no {} window", audience.as_str()))
+}
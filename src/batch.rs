@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::client::{Client, Endpoints};
+use crate::error::{MMCError, MMCResult};
+
+/// The number of operations a [BatchExecutor](struct.BatchExecutor.html) runs at once by default
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// The kinds of change that can be made by a [BatchOperation](struct.BatchOperation.html)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operation {
+    /// Creates a new object beneath a parent object
+    Create,
+
+    /// Updates an existing object
+    Update,
+
+    /// Deletes an existing object
+    Delete,
+
+    /// Moves an existing object to a new parent object
+    Move,
+}
+
+/// A single change to be made as part of a batch
+///
+/// Each kind of operation requires a different set of fields:
+///
+///  * `create` requires a `parent`, `parent_id` and `body`
+///  * `update` requires an `id` and `body`
+///  * `delete` requires an `id`
+///  * `move` requires an `id`, `parent` and `parent_id`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BatchOperation {
+    /// The kind of change to make
+    pub operation: Operation,
+
+    /// The endpoint of the object being changed
+    pub endpoint: Endpoints,
+
+    /// The id of the object being changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// The endpoint of the parent object to create beneath or move to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Endpoints>,
+
+    /// The id of the parent object to create beneath or move to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+
+    /// The request body to send for creates and updates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+impl BatchOperation {
+    /// Checks that the fields required by the kind of operation are present
+    pub fn validate(&self) -> MMCResult<()> {
+        let missing = match self.operation {
+            Operation::Create => self.first_missing(&["parent", "parent_id", "body"]),
+            Operation::Update => self.first_missing(&["id", "body"]),
+            Operation::Delete => self.first_missing(&["id"]),
+            Operation::Move => self.first_missing(&["id", "parent", "parent_id"]),
+        };
+
+        match missing {
+            Some(field) => Err(MMCError::InvalidOperation(format!(
+                "{:?} operations require the {} field",
+                self.operation, field
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn first_missing(&self, fields: &[&'static str]) -> Option<&'static str> {
+        fields.iter().cloned().find(|&field| match field {
+            "id" => self.id.is_none(),
+            "parent" => self.parent.is_none(),
+            "parent_id" => self.parent_id.is_none(),
+            "body" => self.body.is_none(),
+            _ => false,
+        })
+    }
+
+    // Callers must validate the operation first, as missing fields are replaced with defaults
+    fn execute(&self, client: &Client) -> MMCResult<String> {
+        let id = self.id.as_deref().unwrap_or_default();
+        let parent = self.parent.clone().unwrap_or(Endpoints::Show);
+        let parent_id = self.parent_id.as_deref().unwrap_or_default();
+        let body = self.body.as_ref().unwrap_or(&Value::Null);
+
+        match self.operation {
+            Operation::Create => client.create(parent, parent_id, self.endpoint.clone(), body),
+            Operation::Update => client.update(self.endpoint.clone(), id, body),
+            Operation::Delete => client.delete(self.endpoint.clone(), id),
+            Operation::Move => client.change_parent(parent, parent_id, self.endpoint.clone(), id),
+        }
+    }
+}
+
+/// The outcome of a single [BatchOperation](struct.BatchOperation.html)
+#[derive(Debug)]
+pub enum BatchOutcome {
+    /// The operation succeeded with the contained response body
+    Succeeded(String),
+
+    /// The operation failed with the contained error
+    Failed(MMCError),
+
+    /// The operation was not attempted because an earlier operation failed
+    Skipped,
+}
+
+impl BatchOutcome {
+    /// Returns true when the operation succeeded
+    pub fn is_success(&self) -> bool {
+        matches!(*self, BatchOutcome::Succeeded(_))
+    }
+}
+
+/// Runs a list of [BatchOperation](struct.BatchOperation.html)s against a
+/// [Client](struct.Client.html), several at a time
+///
+/// By default every operation is attempted even when others fail. The outcomes are returned in
+/// the same order as the operations were supplied.
+#[derive(Debug)]
+pub struct BatchExecutor<'a> {
+    client: &'a Client,
+    concurrency: usize,
+    continue_on_error: bool,
+}
+
+impl<'a> BatchExecutor<'a> {
+    /// Creates an executor that runs operations with the supplied client
+    pub fn new(client: &'a Client) -> BatchExecutor<'a> {
+        BatchExecutor {
+            client,
+            concurrency: DEFAULT_CONCURRENCY,
+            continue_on_error: true,
+        }
+    }
+
+    /// Sets the maximum number of operations to run at once. Values below one are treated as one.
+    pub fn with_concurrency(mut self, concurrency: usize) -> BatchExecutor<'a> {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets whether operations that have not yet started should still be attempted after an
+    /// operation has failed. Operations that are not attempted are reported as skipped.
+    pub fn continue_on_error(mut self, continue_on_error: bool) -> BatchExecutor<'a> {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Runs every operation, returning their outcomes in the order they were supplied
+    pub fn run(&self, operations: &[BatchOperation]) -> Vec<BatchOutcome> {
+        let next = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let outcomes: Mutex<Vec<Option<BatchOutcome>>> =
+            Mutex::new(operations.iter().map(|_| None).collect());
+
+        thread::scope(|scope| {
+            for _ in 0..self.concurrency.min(operations.len()) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+
+                    let operation = match operations.get(index) {
+                        Some(operation) => operation,
+                        None => return,
+                    };
+
+                    let outcome = if !self.continue_on_error && failed.load(Ordering::SeqCst) {
+                        BatchOutcome::Skipped
+                    } else {
+                        match operation
+                            .validate()
+                            .and_then(|_| operation.execute(self.client))
+                        {
+                            Ok(body) => BatchOutcome::Succeeded(body),
+                            Err(err) => {
+                                failed.store(true, Ordering::SeqCst);
+                                BatchOutcome::Failed(err)
+                            }
+                        }
+                    };
+
+                    outcomes
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())[index] = Some(outcome);
+                });
+            }
+        });
+
+        outcomes
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .into_iter()
+            .map(|outcome| outcome.unwrap_or(BatchOutcome::Skipped))
+            .collect()
+    }
+}
@@ -0,0 +1,290 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use mm_client::{BatchExecutor, BatchOperation, BatchOutcome, Client, DEFAULT_CONCURRENCY};
+use serde_json::{Map, Value};
+
+use std::fs::{self, File};
+use std::io::{self, Read};
+
+use crate::error::CLIError;
+use crate::output::Output;
+use crate::write::{confirm, yes_arg};
+
+// The columns shown for each line of the report when printed as a table or CSV
+pub const REPORT_COLUMNS: [&str; 6] = ["line", "operation", "endpoint", "id", "status", "error"];
+
+const SUCCEEDED: &str = "succeeded";
+const PLANNED: &str = "planned";
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("batch")
+        .about("Applies the creates, updates, deletes and moves listed in an NDJSON or CSV file")
+        .arg(Arg::with_name("file").required(true).help(
+            "File listing one operation per line. Reads from stdin if -, which requires --yes",
+        ))
+        .arg(
+            Arg::with_name("input-format")
+                .long("input-format")
+                .takes_value(true)
+                .possible_values(&["ndjson", "csv"])
+                .help("Format of the file, defaults to csv for .csv files and ndjson otherwise"),
+        )
+        .arg(
+            Arg::with_name("concurrency")
+                .long("concurrency")
+                .takes_value(true)
+                .validator(|value| {
+                    value
+                        .parse::<usize>()
+                        .map(|_| ())
+                        .map_err(|_| "The concurrency must be a whole number".to_string())
+                })
+                .help("Number of operations to run at once, defaults to 4"),
+        )
+        .arg(
+            Arg::with_name("stop-on-error")
+                .long("stop-on-error")
+                .takes_value(false)
+                .help("Skips the remaining operations once an operation fails"),
+        )
+        .arg(
+            Arg::with_name("report")
+                .long("report")
+                .takes_value(true)
+                .help(
+                    "Writes the per line results to this file as NDJSON instead of printing them",
+                ),
+        )
+        .arg(yes_arg())
+}
+
+pub fn run(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let file = matches.value_of("file").unwrap_or("-");
+    let csv = match matches.value_of("input-format") {
+        Some(format) => format == "csv",
+        None => file.ends_with(".csv"),
+    };
+
+    // Stdin is read to its end before the prompt, so there would be nothing left to answer it
+    if file == "-" && !matches.is_present("yes") && !matches.is_present("dry-run") {
+        return Err(CLIError::StdinNeedsYes);
+    }
+
+    let mut contents = String::new();
+    match file {
+        "-" => io::stdin().read_to_string(&mut contents)?,
+        path => File::open(path)?.read_to_string(&mut contents)?,
+    };
+
+    let lines = if csv {
+        parse_csv(contents.as_str())
+    } else {
+        parse_ndjson(contents.as_str())
+    };
+
+    let operations: Vec<BatchOperation> = lines
+        .iter()
+        .filter_map(|(_, parsed)| parsed.as_ref().ok().cloned())
+        .collect();
+
+    confirm(
+        matches,
+        format!("Apply {} operations from {}?", operations.len(), file).as_str(),
+    )?;
+
+    let concurrency = matches
+        .value_of("concurrency")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_CONCURRENCY);
+
+    let mut outcomes = BatchExecutor::new(client)
+        .with_concurrency(concurrency)
+        .continue_on_error(!matches.is_present("stop-on-error"))
+        .run(&operations)
+        .into_iter();
+
+    // Lines that could not be parsed were never run, so their errors take the place of an outcome
+    let report: Vec<Value> = lines
+        .iter()
+        .map(|(line, parsed)| match parsed {
+            Ok(operation) => report_line(*line, Some(operation), outcomes.next(), client),
+            Err(err) => {
+                let mut entry = report_line(*line, None, None, client);
+                entry["error"] = Value::String(err.clone());
+                entry
+            }
+        })
+        .collect();
+
+    let count = |status: &str| {
+        report
+            .iter()
+            .filter(|entry| entry["status"] == status)
+            .count()
+    };
+    let summary = format!(
+        "{} {}, {} failed, {} skipped",
+        count(SUCCEEDED) + count(PLANNED),
+        if client.is_dry_run() {
+            PLANNED
+        } else {
+            SUCCEEDED
+        },
+        count("failed"),
+        count("skipped")
+    );
+
+    match matches.value_of("report") {
+        Some(path) => {
+            let lines: Vec<String> = report.iter().map(Value::to_string).collect();
+            fs::write(path, lines.join("\n") + "\n")?;
+
+            Ok(Output::Message(format!(
+                "{}. Results written to {}",
+                summary, path
+            )))
+        }
+        None => {
+            eprintln!("{}", summary);
+            Ok(Output::Json(serde_json::json!({ "data": report })))
+        }
+    }
+}
+
+// Dry runs report the request that would have been sent in place of the response
+fn report_line(
+    line: usize,
+    operation: Option<&BatchOperation>,
+    outcome: Option<BatchOutcome>,
+    client: &Client,
+) -> Value {
+    let mut entry = serde_json::json!({ "line": line });
+
+    if let Some(operation) = operation {
+        entry["operation"] = serde_json::to_value(operation.operation).unwrap_or(Value::Null);
+        entry["endpoint"] = Value::String(operation.endpoint.to_string());
+        entry["id"] = operation.id.clone().map_or(Value::Null, Value::String);
+    }
+
+    let (status, error) = match outcome {
+        Some(BatchOutcome::Succeeded(ref body)) if client.is_dry_run() => {
+            entry["request"] = serde_json::from_str(body).unwrap_or(Value::Null);
+            (PLANNED, None)
+        }
+        Some(BatchOutcome::Succeeded(_)) => (SUCCEEDED, None),
+        Some(BatchOutcome::Failed(err)) => ("failed", Some(err.to_string())),
+        Some(BatchOutcome::Skipped) => ("skipped", None),
+        None => ("failed", None),
+    };
+
+    entry["status"] = Value::String(status.to_string());
+
+    if let Some(error) = error {
+        entry["error"] = Value::String(error);
+    }
+
+    entry
+}
+
+type ParsedLine = (usize, Result<BatchOperation, String>);
+
+// Parses one JSON operation per line, numbering lines from one and skipping blank lines
+fn parse_ndjson(contents: &str) -> Vec<ParsedLine> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let parsed = serde_json::from_str(line).map_err(|err| err.to_string());
+            (index + 1, parsed)
+        })
+        .collect()
+}
+
+// Parses a CSV file whose header names the operation, endpoint, id, parent, parent_id and body
+// columns. The body column holds the request body as JSON. Records are numbered by the line they
+// start on, as quoted fields may span several lines.
+fn parse_csv(contents: &str) -> Vec<ParsedLine> {
+    let mut records = split_csv_records(contents).into_iter();
+
+    let header = match records.next() {
+        Some((_, header)) => header,
+        None => return vec![],
+    };
+
+    records
+        .map(|(line, record)| {
+            let mut fields = Map::new();
+
+            for (name, value) in header.iter().zip(record) {
+                if value.is_empty() {
+                    continue;
+                }
+
+                let value = if name == "body" {
+                    match serde_json::from_str(value.as_str()) {
+                        Ok(body) => body,
+                        Err(err) => return (line, Err(format!("Invalid body. {}", err))),
+                    }
+                } else {
+                    Value::String(value)
+                };
+
+                fields.insert(name.clone(), value);
+            }
+
+            let parsed =
+                serde_json::from_value(Value::Object(fields)).map_err(|err| err.to_string());
+            (line, parsed)
+        })
+        .collect()
+}
+
+// Splits CSV into records of fields along with the line each record starts on, unquoting fields
+// wrapped in double quotes and skipping blank lines. Line breaks within quoted fields are kept.
+fn split_csv_records(contents: &str) -> Vec<(usize, Vec<String>)> {
+    let mut records = vec![];
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(field.split_off(0)),
+            '\n' if quoted => {
+                field.push(c);
+                line += 1;
+            }
+            '\n' => {
+                fields.push(field.split_off(0));
+                records.push((start, fields.split_off(0)));
+                line += 1;
+                start = line;
+            }
+            '\r' if !quoted && chars.peek() == Some(&'\n') => (),
+            c => field.push(c),
+        }
+    }
+
+    fields.push(field);
+    records.push((start, fields));
+
+    records
+        .into_iter()
+        .filter(|(_, fields)| fields.iter().any(|field| !field.trim().is_empty()))
+        .map(|(line, fields)| {
+            let fields = fields
+                .iter()
+                .map(|field| field.trim().to_string())
+                .collect();
+            (line, fields)
+        })
+        .collect()
+}
@@ -0,0 +1,280 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use mm_client::{Client, MMCError};
+use serde_json::Value;
+
+use std::cmp;
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::CLIError;
+use crate::output::{cell, lookup, Output};
+
+// The longest wait between polls after the changelog repeatedly fails to be fetched
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("changelog")
+        .about("Lists recent changes, optionally following new changes as they appear")
+        .arg(
+            Arg::with_name("since")
+                .long("since")
+                .takes_value(true)
+                .validator(|value| {
+                    utc_timestamp(value.as_str())
+                        .map(|_| ())
+                        .ok_or_else(|| "The timestamp must be in RFC 3339 form".to_string())
+                })
+                .help(
+                    "Only lists changes made after this RFC 3339 timestamp, such as \
+                     2020-01-01T00:00:00Z. Defaults to 24 hours ago, or to now when following",
+                ),
+        )
+        .arg(
+            Arg::with_name("type")
+                .long("type")
+                .takes_value(true)
+                .help("Only lists changes to this type of resource"),
+        )
+        .arg(
+            Arg::with_name("action")
+                .long("action")
+                .takes_value(true)
+                .possible_values(&["create", "update", "delete"])
+                .help("Only lists changes made by this action"),
+        )
+        .arg(
+            Arg::with_name("follow")
+                .short("f")
+                .long("follow")
+                .takes_value(false)
+                .help("Keeps polling for new changes and prints them as they appear"),
+        )
+        .arg(
+            Arg::with_name("interval")
+                .long("interval")
+                .takes_value(true)
+                .default_value("30")
+                .validator(|value| {
+                    value
+                        .parse::<u64>()
+                        .map(|_| ())
+                        .map_err(|_| "The interval must be a whole number of seconds".to_string())
+                })
+                .help("Seconds to wait between polls when following"),
+        )
+        .arg(
+            Arg::with_name("summary")
+                .long("summary")
+                .takes_value(false)
+                .help("Prints a one line summary per change"),
+        )
+}
+
+pub fn run(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let mut params = vec![];
+
+    for name in &["type", "action"] {
+        if let Some(value) = matches.value_of(name) {
+            params.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    // Without a starting point the whole changelog would be crawled
+    let since = match matches.value_of("since") {
+        Some(since) => utc_timestamp(since).unwrap_or_else(|| since.to_string()),
+        None if matches.is_present("follow") => format_timestamp(Utc::now()),
+        None => format_timestamp(Utc::now() - chrono::Duration::days(1)),
+    };
+    let summary = matches.is_present("summary");
+
+    if matches.is_present("follow") {
+        let interval = matches
+            .value_of("interval")
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(30);
+
+        return follow(
+            client,
+            params,
+            since,
+            Duration::from_secs(interval),
+            summary,
+        );
+    }
+
+    let entries = fetch(client, &params, since.as_str())?;
+
+    if summary {
+        Ok(Output::Message(
+            entries
+                .iter()
+                .map(summarize)
+                .collect::<Vec<String>>()
+                .join("\n"),
+        ))
+    } else {
+        Ok(Output::Json(serde_json::json!({ "data": entries })))
+    }
+}
+
+// Polls the changelog until interrupted, printing each change once. The changelog is queried
+// from the newest timestamp seen so far, and the changes already printed at that timestamp are
+// remembered so that they are not repeated by the next poll. Polls that fail because the API
+// could not be reached or had a server error are reported and retried after a growing delay.
+fn follow(
+    client: &Client,
+    params: Vec<(String, String)>,
+    mut since: String,
+    interval: Duration,
+    summary: bool,
+) -> Result<Output, CLIError> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut backoff = interval;
+
+    loop {
+        let entries = match fetch(client, &params, since.as_str()) {
+            Ok(entries) => entries,
+            Err(CLIError::Network(ref err)) if is_transient(err) => {
+                backoff = cmp::min(
+                    cmp::max(backoff * 2, Duration::from_secs(1)),
+                    cmp::max(interval, MAX_BACKOFF),
+                );
+                eprintln!(
+                    "Unable to fetch the changelog, retrying in {} seconds. {}",
+                    backoff.as_secs(),
+                    err
+                );
+                thread::sleep(backoff);
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+
+        backoff = interval;
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+
+        for entry in entries {
+            let key = entry.to_string();
+
+            if seen.contains(&key) {
+                continue;
+            }
+
+            let line = if summary {
+                summarize(&entry)
+            } else {
+                key.clone()
+            };
+            writeln!(out, "{}", line)?;
+
+            if let Some(timestamp) = timestamp(&entry) {
+                if timestamp > since {
+                    since = timestamp;
+                    seen.clear();
+                }
+            }
+
+            seen.insert(key);
+        }
+
+        out.flush()?;
+        drop(out);
+
+        thread::sleep(interval);
+    }
+}
+
+fn fetch(
+    client: &Client,
+    params: &[(String, String)],
+    since: &str,
+) -> Result<Vec<Value>, CLIError> {
+    let mut params: Vec<(&str, &str)> = params
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    params.push(("since", since));
+
+    let mut entries = client.changelog_all(params)?;
+    entries.sort_by_key(timestamp);
+
+    Ok(entries)
+}
+
+fn is_transient(err: &MMCError) -> bool {
+    match *err {
+        MMCError::Network(_) | MMCError::Io(_) => true,
+        MMCError::APIFailure(status) => status.is_server_error(),
+        _ => false,
+    }
+}
+
+// Converts an RFC 3339 timestamp to UTC, so that it compares correctly with the timestamps of
+// the changes seen when following
+fn utc_timestamp(value: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(value).ok().map(|instant| {
+        instant
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+    })
+}
+
+fn format_timestamp(instant: DateTime<Utc>) -> String {
+    instant.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+fn timestamp(entry: &Value) -> Option<String> {
+    lookup(entry, "attributes.timestamp")
+        .and_then(Value::as_str)
+        .map(String::from)
+}
+
+// Formats a change as `<timestamp> <action> <type> <id> (<updated fields>)`
+fn summarize(entry: &Value) -> String {
+    let mut line = ["attributes.timestamp", "attributes.action", "type", "id"]
+        .iter()
+        .map(|path| cell(lookup(entry, path)))
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    if let Some(Value::Array(fields)) = lookup(entry, "attributes.updated_fields") {
+        let fields: Vec<String> = fields.iter().map(|field| cell(Some(field))).collect();
+
+        if !fields.is_empty() {
+            line.push_str(format!(" ({})", fields.join(", ")).as_str());
+        }
+    }
+
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::StatusCode;
+
+    #[test]
+    fn timestamps_are_converted_to_utc() {
+        assert_eq!(
+            utc_timestamp("2020-01-01T01:30:00+01:00"),
+            Some("2020-01-01T00:30:00Z".to_string())
+        );
+        assert_eq!(utc_timestamp("2020-01-01"), None);
+    }
+
+    #[test]
+    fn only_unreachable_or_failing_servers_are_retried() {
+        assert!(is_transient(&MMCError::APIFailure(
+            StatusCode::SERVICE_UNAVAILABLE
+        )));
+        assert!(!is_transient(&MMCError::NotAuthorized));
+        assert!(!is_transient(&MMCError::APIFailure(
+            StatusCode::TOO_MANY_REQUESTS
+        )));
+    }
+}
@@ -0,0 +1,320 @@
+use clap::{App, Arg, ArgMatches, Shell, SubCommand};
+use mm_client::Endpoints;
+
+use crate::error::CLIError;
+use crate::output::Output;
+
+const BIN: &str = "mm_cli";
+
+const SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+// The positional arguments that take an object type, as the subcommand and the number of
+// positional arguments given to it before the type
+const TYPE_POSITIONS: [&str; 11] = [
+    "get:0",
+    "list:0",
+    "children:0",
+    "children:2",
+    "create:0",
+    "create:2",
+    "update:0",
+    "delete:0",
+    "move:0",
+    "move:2",
+    "edit:0",
+];
+
+// Every option that takes a value, whose value is not a subcommand or positional argument
+const VALUE_OPTIONS: [&str; 28] = [
+    "-f",
+    "-o",
+    "-p",
+    "--action",
+    "--audit-log",
+    "--base-url",
+    "--columns",
+    "--concurrency",
+    "--days",
+    "--environment",
+    "--fields",
+    "--file",
+    "--input-format",
+    "--interval",
+    "--journal",
+    "--key",
+    "--operator",
+    "--output",
+    "--page",
+    "--page-size",
+    "--param",
+    "--profile",
+    "--report",
+    "--secret",
+    "--show",
+    "--since",
+    "--type",
+    "--where",
+];
+
+const PROFILE_NAMES: &str = "$(mm_cli profile list 2>/dev/null | cut -f1)";
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("completions")
+        .about("Prints a shell completion script for bash, zsh or fish")
+        .arg(
+            Arg::with_name("shell")
+                .required(true)
+                .possible_values(&SHELLS)
+                .help("Shell to generate completions for"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<Output, CLIError> {
+    let script = match matches.value_of("shell") {
+        Some("zsh") => zsh(),
+        Some("fish") => fish(),
+        _ => bash(),
+    };
+
+    Ok(Output::Message(script))
+}
+
+// The subcommands and flags are completed by the script clap generates from the app, which the
+// profile names and object types that clap does not know about are then added to
+fn generated(shell: Shell) -> String {
+    let mut script = vec![];
+    crate::app().gen_completions_to(BIN, shell, &mut script);
+
+    String::from_utf8_lossy(&script).trim_end().to_string()
+}
+
+// Every name accepted by Endpoints::from_str, in both singular and plural form
+fn endpoint_names() -> Vec<String> {
+    let mut names: Vec<String> = Endpoints::all()
+        .iter()
+        .flat_map(|endpoint| vec![endpoint.singular(), endpoint.to_string()])
+        .collect();
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+// The completion function is shared by bash and zsh, which differ only in how the words on the
+// command line are accessed and how candidates are offered. The words before the one being
+// completed are walked to find the subcommand and how many positional arguments it has been
+// given, skipping options along with their values. Anything that is not a profile name or an
+// object type in a type position is left to the generated function.
+struct Posix {
+    first_word: usize,
+    current_index: &'static str,
+    word: &'static str,
+    current: &'static str,
+    previous: &'static str,
+    reply: fn(&str) -> String,
+}
+
+impl Posix {
+    fn function(&self) -> String {
+        let reply = self.reply;
+
+        format!(
+            r#"_{bin}_dynamic() {{
+    local cur="{current}" prev="{previous}" command="" position=0 i={first}
+    while [ $i -lt {index} ]; do
+        case "{word}" in
+            {value_options}) i=$((i + 2)); continue ;;
+            -*) ;;
+            *)
+                if [ -z "$command" ]; then
+                    command="{word}"
+                else
+                    position=$((position + 1))
+                fi
+                ;;
+        esac
+        i=$((i + 1))
+    done
+
+    case "$prev" in
+        -p|--profile) {profiles}; return ;;
+        --type) {endpoints}; return ;;
+        {value_options}) _{bin} "$@"; return ;;
+    esac
+
+    case "$command:$position:$cur" in
+        *:-*) ;;
+        {type_positions}) {endpoints}; return ;;
+        profile:1:*) if [ "$prev" = remove ]; then {profiles}; return; fi ;;
+    esac
+
+    _{bin} "$@"
+}}"#,
+            bin = BIN,
+            current = self.current,
+            previous = self.previous,
+            first = self.first_word,
+            index = self.current_index,
+            word = self.word,
+            profiles = reply(PROFILE_NAMES),
+            endpoints = reply(endpoint_names().join(" ").as_str()),
+            value_options = VALUE_OPTIONS.join("|"),
+            type_positions = TYPE_POSITIONS
+                .iter()
+                .map(|position| format!("{}:*", position))
+                .collect::<Vec<String>>()
+                .join("|"),
+        )
+    }
+}
+
+fn bash() -> String {
+    let posix = Posix {
+        first_word: 1,
+        current_index: "$COMP_CWORD",
+        word: "${COMP_WORDS[i]}",
+        current: "${COMP_WORDS[COMP_CWORD]}",
+        previous: "${COMP_WORDS[COMP_CWORD-1]}",
+        reply: |words| format!("COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", words),
+    };
+
+    format!(
+        "{generated}\n\n{function}\n\ncomplete -F _{bin}_dynamic -o bashdefault -o default {bin}\n",
+        generated = generated(Shell::Bash),
+        function = posix.function(),
+        bin = BIN,
+    )
+}
+
+// The generated script ends by calling its completion function, for when it is autoloaded from
+// $fpath. That call is replaced so that the first completion also goes through the dynamic
+// function, and it is skipped when the script is sourced instead.
+fn zsh() -> String {
+    let posix = Posix {
+        first_word: 2,
+        current_index: "$CURRENT",
+        word: "${words[i]}",
+        current: "${words[CURRENT]}",
+        previous: "${words[CURRENT-1]}",
+        reply: |words| format!("compadd -- {}", words),
+    };
+
+    let generated = generated(Shell::Zsh);
+    let call = format!("_{} \"$@\"", BIN);
+
+    format!(
+        r#"{generated}
+
+{function}
+
+compdef _{bin}_dynamic {bin}
+
+if [ "$funcstack[1]" = _{bin} ]; then
+    _{bin}_dynamic "$@"
+fi
+"#,
+        generated = generated.trim_end_matches(call.as_str()).trim_end(),
+        function = posix.function(),
+        bin = BIN,
+    )
+}
+
+// Object types are offered by a condition that walks the command line in the same way as the
+// bash and zsh function
+fn fish() -> String {
+    let profiles = PROFILE_NAMES.trim_start_matches('$');
+    let endpoints = endpoint_names().join(" ");
+
+    let lines = [
+        generated(Shell::Fish),
+        format!(
+            r#"function __{bin}_type_position
+    set -l words (commandline -opc)
+    set -e words[1]
+    set -l command ""
+    set -l position 0
+    set -l skip 0
+    for word in $words
+        if test $skip -eq 1
+            set skip 0
+            continue
+        end
+        switch $word
+            case {value_options}
+                set skip 1
+            case '-*'
+            case '*'
+                if test -z "$command"
+                    set command $word
+                else
+                    set position (math $position + 1)
+                end
+        end
+    end
+    contains -- "$command:$position" {type_positions}
+end"#,
+            bin = BIN,
+            value_options = VALUE_OPTIONS.join(" "),
+            type_positions = TYPE_POSITIONS.join(" "),
+        ),
+        format!("complete -c {} -s p -l profile -x -a '{}'", BIN, profiles),
+        format!(
+            "complete -c {bin} -n '__{bin}_type_position' -f -a '{}'",
+            endpoints,
+            bin = BIN
+        ),
+        format!(
+            "complete -c {} -n '__fish_seen_subcommand_from changelog' -l type -x -a '{}'",
+            BIN, endpoints
+        ),
+        format!(
+            "complete -c {} -n '__fish_seen_subcommand_from remove' -f -a '{}'",
+            BIN, profiles
+        ),
+    ];
+
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_complete_generated_subcommands() {
+        for script in [bash(), zsh(), fish()].iter() {
+            assert!(script.contains("changelog"));
+            assert!(script.contains("dry-run"));
+            assert!(script.contains("mm_cli profile list"));
+            assert!(script.contains("episodes"));
+        }
+    }
+
+    // The options that take a value are those clap completes the value of in the bash script
+    #[test]
+    fn value_options_match_generated_script() {
+        let script = generated(Shell::Bash);
+        let mut options: Vec<&str> = script
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with('-') && line.ends_with(')'))
+            .map(|line| line.trim_end_matches(')'))
+            .collect();
+
+        options.sort();
+        options.dedup();
+
+        let mut expected = VALUE_OPTIONS.to_vec();
+        expected.sort();
+
+        assert_eq!(options, expected);
+    }
+
+    #[test]
+    fn zsh_script_does_not_call_itself_when_sourced() {
+        let script = zsh();
+
+        assert!(!script.contains("\n_mm_cli \"$@\""));
+        assert!(script.contains("compdef _mm_cli_dynamic mm_cli"));
+    }
+}
@@ -1,12 +1,17 @@
+use mm_client::{Client, CredentialProvider, Credentials, Environment, MMCError, MMCResult};
+
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{self, BufRead, Read, Write};
 
 use crate::error::CLIError;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Config {
     pub live: Option<EndpointConfig>,
     pub staging: Option<EndpointConfig>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -15,6 +20,40 @@ pub struct EndpointConfig {
     pub secret: String,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Profile {
+    pub key: String,
+    pub secret: String,
+    #[serde(default = "Profile::default_environment")]
+    pub environment: Environment,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+}
+
+impl Profile {
+    fn default_environment() -> Environment {
+        Environment::Live
+    }
+
+    pub fn client(&self) -> MMCResult<Client> {
+        match (self.base_url.as_ref(), self.environment) {
+            (Some(base), _) => Client::custom(self.key.as_str(), self.secret.as_str(), base),
+            (None, Environment::Live) => Client::new(self.key.as_str(), self.secret.as_str()),
+            (None, Environment::Staging) => {
+                Client::staging(self.key.as_str(), self.secret.as_str())
+            }
+        }
+    }
+
+    // Describes where the profile sends requests for display to the user
+    pub fn target(&self) -> String {
+        match self.base_url {
+            Some(ref base) => base.to_string(),
+            None => self.environment.to_string(),
+        }
+    }
+}
+
 impl Config {
     pub fn parse_config(path: &str) -> Result<Config, CLIError> {
         let mut config_toml = String::new();
@@ -29,26 +68,25 @@ impl Config {
             })
     }
 
-    pub fn create(path: &str) -> Result<Config, CLIError> {
-        let new_config = Config::generate();
-        new_config.store(path).and_then(|_| Ok(new_config))
+    // Loads the config at the given path, starting from an empty config if none exists yet
+    pub fn load_or_default(path: &str) -> Result<Config, CLIError> {
+        match Config::parse_config(path) {
+            Err(CLIError::ConfigStorageFailure(ref err))
+                if err.kind() == io::ErrorKind::NotFound =>
+            {
+                Ok(Config::default())
+            }
+            result => result,
+        }
     }
 
-    fn generate() -> Config {
-        println!("\x1B[1mconfig.toml Generator\x1B[0m");
-        Config {
-            live: Some(EndpointConfig {
-                key: Config::prompt_for_input("Production API Key: "),
-                secret: Config::prompt_for_input("Production API Secret: "),
-            }),
-            staging: Some(EndpointConfig {
-                key: Config::prompt_for_input("Staging API Key: "),
-                secret: Config::prompt_for_input("Staging API Secret: "),
-            }),
-        }
+    pub fn profile(&self, name: &str) -> Result<&Profile, CLIError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| CLIError::UnknownProfile(name.to_string()))
     }
 
-    fn store(&self, path: &str) -> Result<(), CLIError> {
+    pub fn store(&self, path: &str) -> Result<(), CLIError> {
         let mut file = File::create(path)?;
         file.write_all(
             &toml::to_string(&self)
@@ -58,17 +96,29 @@ impl Config {
         .map_err(CLIError::ConfigStorageFailure)
     }
 
-    fn prompt_for_input(prompt: &str) -> String {
+    pub fn prompt_for_input(prompt: &str) -> String {
         print!("{}", prompt);
-        io::stdout().flush();
+        let _ = io::stdout().flush();
         let stdin = io::stdin();
-        let input = stdin
+
+        stdin
             .lock()
             .lines()
             .next()
             .expect("Input could not be found")
-            .expect("Input could not be read");
+            .expect("Input could not be read")
+    }
+}
+
+impl CredentialProvider for Config {
+    fn credentials(&self, environment: Environment) -> MMCResult<Credentials> {
+        let conf = match environment {
+            Environment::Live => &self.live,
+            Environment::Staging => &self.staging,
+        };
 
-        input
+        conf.as_ref()
+            .map(|key_sec| Credentials::new(key_sec.key.as_str(), key_sec.secret.as_str()))
+            .ok_or_else(|| MMCError::MissingCredentials(format!("{} in config.toml", environment)))
     }
 }
@@ -0,0 +1,146 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use mm_client::{AttributeDiff, Client, ConflictPolicy, Endpoints};
+use serde_json::Value;
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::CLIError;
+use crate::list::parse_endpoint;
+use crate::output::Output;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("edit")
+        .about("Opens the edit object in $EDITOR and updates any attributes that were changed")
+        .arg(
+            Arg::with_name("type")
+                .required(true)
+                .help("Object type to edit"),
+        )
+        .arg(
+            Arg::with_name("id")
+                .required(true)
+                .help("Object id to edit"),
+        )
+        .arg(
+            Arg::with_name("force")
+                .long("force")
+                .takes_value(false)
+                .help("Overwrites attributes that were changed on the server while editing"),
+        )
+}
+
+pub fn run(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let endpoint = parse_endpoint(matches.value_of("type"))?;
+    let id = matches.value_of("id").unwrap_or_default();
+
+    let policy = if matches.is_present("force") {
+        ConflictPolicy::Warn
+    } else {
+        ConflictPolicy::Refuse
+    };
+
+    edit(client, endpoint, id, policy)
+}
+
+pub fn edit(
+    client: &Client,
+    endpoint: Endpoints,
+    id: &str,
+    policy: ConflictPolicy,
+) -> Result<Output, CLIError> {
+    let current: Value = serde_json::from_str(client.edit(endpoint.clone(), id)?.as_str())?;
+
+    let contents = edit_in_temp_file(&endpoint, id, &current)?;
+
+    // The file has been removed, so edits that can not be parsed are printed to keep them
+    let desired: Value = match serde_json::from_str(contents.as_str()) {
+        Ok(desired) => desired,
+        Err(err) => {
+            eprintln!(
+                "The edits could not be parsed and have not been saved:\n{}",
+                contents
+            );
+            return Err(CLIError::InvalidBody(err));
+        }
+    };
+
+    // Only the attributes changed in the editor are sent, so that attributes changed on the
+    // server in the meantime are left alone
+    let edits = AttributeDiff::between(&current, &desired)?;
+
+    if edits.is_empty() {
+        return Ok(Output::Message(format!(
+            "No changes made to {}/{}",
+            endpoint, id
+        )));
+    }
+
+    let result = client.update_changed_since(
+        endpoint.clone(),
+        id,
+        &serde_json::json!({ "attributes": edits.changed }),
+        &current,
+        policy,
+    )?;
+
+    if !result.conflicts.is_empty() {
+        eprintln!(
+            "Overwrote {}, which had been changed on the server since it was fetched",
+            result.conflicts.join(", ")
+        );
+    }
+
+    match result.response {
+        Some(response) if client.is_dry_run() => crate::handle_client_response(Ok(response)),
+        Some(_) => Ok(Output::Message(format!(
+            "Updated {} on {}/{}",
+            result.diff.names().join(", "),
+            endpoint,
+            id
+        ))),
+        None => Ok(Output::Message(format!(
+            "No changes made to {}/{} as it already matches the edits",
+            endpoint, id
+        ))),
+    }
+}
+
+// Writes the object to a temporary file, opens it in the editor and returns the edited contents.
+// The file is created with a random name that only the current user can read or write, and is
+// removed however editing ends.
+fn edit_in_temp_file(endpoint: &Endpoints, id: &str, current: &Value) -> Result<String, CLIError> {
+    let mut file = tempfile::Builder::new()
+        .prefix(format!("mm_cli-{}-{}-", endpoint, id).as_str())
+        .suffix(".json")
+        .tempfile()?;
+
+    file.write_all(serde_json::to_string_pretty(current)?.as_bytes())?;
+    file.flush()?;
+
+    open_editor(file.path())?;
+
+    // Editors may replace the file rather than write to it, so it is read again by its path
+    Ok(fs::read_to_string(file.path())?)
+}
+
+// Runs $VISUAL or $EDITOR (falling back to vi) against the file and waits for it to exit
+fn open_editor(path: &Path) -> Result<(), CLIError> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+
+    let status = Command::new(program).args(parts).arg(path).status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(CLIError::Aborted)
+    }
+}
@@ -1,9 +1,11 @@
-extern crate serde_json;
 extern crate mm_client;
+extern crate serde_json;
+
+use rustyline::error::ReadlineError;
 
 use std::error::Error;
-use std::io;
 use std::fmt;
+use std::io;
 
 use self::mm_client::MMCError;
 
@@ -12,6 +14,18 @@ pub enum CLIError {
     InvalidConfig,
     EndpointConfigMissing,
     Endpoint,
+    UnknownProfile(String),
+    InvalidParam(String),
+    MissingId,
+    MissingJournal,
+    Aborted,
+    StdinNeedsYes,
+    InvalidBody(serde_json::error::Error),
+    UnknownFormat(String),
+    InvalidFilter(String),
+    UnknownCommand(String),
+    Shell(ReadlineError),
+    Yaml(serde_yaml::Error),
     ConfigStorageFailure(io::Error),
     Format(serde_json::error::Error),
     Network(MMCError),
@@ -30,7 +44,8 @@ impl fmt::Display for CLIError {
             CLIError::EndpointConfigMissing => {
                 write!(
                     f,
-                    "config.toml is missing the key/secret pair for this endpoint."
+                    "No key/secret pair for this endpoint was found in the environment or \
+                        config.toml."
                 )
             }
             CLIError::Endpoint => {
@@ -39,6 +54,34 @@ impl fmt::Display for CLIError {
                     "Requested endpoint is not in the list of known endpoints."
                 )
             }
+            CLIError::UnknownProfile(ref name) => {
+                write!(f, "config.toml does not contain a profile named {}.", name)
+            }
+            CLIError::InvalidParam(ref param) => {
+                write!(f, "Parameter {} is not in key=value form.", param)
+            }
+            CLIError::MissingId => write!(f, "An object id must be supplied."),
+            CLIError::MissingJournal => write!(
+                f,
+                "A journal must be supplied with --journal or the MM_JOURNAL environment variable."
+            ),
+            CLIError::Aborted => write!(f, "Aborted without making any changes."),
+            CLIError::StdinNeedsYes => write!(
+                f,
+                "Operations read from stdin can not be confirmed. Supply --yes to apply them."
+            ),
+            CLIError::InvalidBody(ref err) => write!(f, "Request body is not valid JSON. {}", err),
+            CLIError::UnknownFormat(ref format) => write!(f, "Unknown output format {}.", format),
+            CLIError::Yaml(ref err) => err.fmt(f),
+            CLIError::InvalidFilter(ref filter) => write!(
+                f,
+                "Invalid filter {}. Filters must be in path=value form, using one of =, !=, ~, >, >=, < or <=.",
+                filter
+            ),
+            CLIError::UnknownCommand(ref command) => {
+                write!(f, "Unknown command {}. Type help for a list of commands.", command)
+            }
+            CLIError::Shell(ref err) => err.fmt(f),
             CLIError::ConfigStorageFailure(ref err) => err.fmt(f),
             CLIError::Format(_) => write!(f, "Failure to format response."),
             CLIError::Network(ref err) => err.fmt(f),
@@ -47,6 +90,7 @@ impl fmt::Display for CLIError {
 }
 
 impl Error for CLIError {
+    #[allow(deprecated)]
     fn description(&self) -> &str {
         match *self {
             CLIError::InvalidConfig => {
@@ -54,20 +98,44 @@ impl Error for CLIError {
                  missing property."
             }
             CLIError::EndpointConfigMissing => {
-                "config.toml is missing the key/secret pair for this endpoint/"
+                "No key/secret pair for this endpoint was found in the environment or config.toml."
             }
             CLIError::Endpoint => "Requested endpoint is not in the list of known endpoints.",
+            CLIError::UnknownProfile(_) => "config.toml does not contain the requested profile.",
+            CLIError::InvalidParam(_) => "Parameter is not in key=value form.",
+            CLIError::MissingId => "An object id must be supplied.",
+            CLIError::MissingJournal => "A journal must be supplied.",
+            CLIError::Aborted => "Aborted without making any changes.",
+            CLIError::StdinNeedsYes => "Operations read from stdin can not be confirmed.",
+            CLIError::InvalidBody(_) => "Request body is not valid JSON.",
+            CLIError::UnknownFormat(_) => "Unknown output format.",
+            CLIError::Yaml(_) => "Unable to format the response as YAML.",
+            CLIError::InvalidFilter(_) => "Invalid filter expression.",
+            CLIError::UnknownCommand(_) => "Unknown shell command.",
+            CLIError::Shell(_) => "Unable to read input from the terminal.",
             CLIError::ConfigStorageFailure(ref err) => err.description(),
             CLIError::Format(_) => "Unable to format the response from the server.",
             CLIError::Network(ref err) => err.description(),
         }
     }
 
-    fn cause(&self) -> Option<&Error> {
+    fn cause(&self) -> Option<&dyn Error> {
         match *self {
             CLIError::InvalidConfig => None,
             CLIError::EndpointConfigMissing => None,
             CLIError::Endpoint => None,
+            CLIError::UnknownProfile(_) => None,
+            CLIError::InvalidParam(_) => None,
+            CLIError::MissingId => None,
+            CLIError::MissingJournal => None,
+            CLIError::Aborted => None,
+            CLIError::StdinNeedsYes => None,
+            CLIError::InvalidBody(ref err) => Some(err),
+            CLIError::UnknownFormat(_) => None,
+            CLIError::InvalidFilter(_) => None,
+            CLIError::Yaml(ref err) => Some(err),
+            CLIError::UnknownCommand(_) => None,
+            CLIError::Shell(ref err) => Some(err),
             CLIError::ConfigStorageFailure(ref err) => Some(err),
             CLIError::Format(ref err) => Some(err),
             CLIError::Network(ref err) => Some(err),
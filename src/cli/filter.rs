@@ -0,0 +1,314 @@
+use clap::{Arg, ArgMatches};
+use serde_json::{Map, Value};
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use crate::error::CLIError;
+use crate::output::{cell, lookup, Output};
+
+// Operators are checked in order, so two character operators must come before their one
+// character prefixes
+const OPERATORS: [(&str, Operator); 7] = [
+    ("!=", Operator::NotEqual),
+    (">=", Operator::GreaterOrEqual),
+    ("<=", Operator::LessOrEqual),
+    ("=", Operator::Equal),
+    ("~", Operator::Contains),
+    (">", Operator::Greater),
+    ("<", Operator::Less),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Contains,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+// A single --where expression such as `attributes.premiered_on>=2020-01-01`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Predicate {
+    path: String,
+    operator: Operator,
+    value: String,
+}
+
+impl FromStr for Predicate {
+    type Err = CLIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CLIError::InvalidFilter(s.to_string());
+
+        let (index, symbol, operator) = s
+            .char_indices()
+            .filter_map(|(index, _)| {
+                OPERATORS
+                    .iter()
+                    .find(|(symbol, _)| s[index..].starts_with(symbol))
+                    .map(|&(symbol, operator)| (index, symbol, operator))
+            })
+            .next()
+            .ok_or_else(invalid)?;
+
+        let path = s[..index].trim();
+
+        if path.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Predicate {
+            path: path.to_string(),
+            operator,
+            value: s[index + symbol.len()..].trim().to_string(),
+        })
+    }
+}
+
+impl Predicate {
+    pub fn matches(&self, record: &Value) -> bool {
+        let found = lookup(record, self.path.as_str());
+
+        match self.operator {
+            Operator::Equal => cell(found) == self.value,
+            Operator::NotEqual => cell(found) != self.value,
+            Operator::Contains => match found {
+                Some(Value::Array(items)) => {
+                    items.iter().any(|item| cell(Some(item)) == self.value)
+                }
+                other => cell(other).contains(self.value.as_str()),
+            },
+            Operator::Greater => self.compare(found) == Some(Ordering::Greater),
+            Operator::GreaterOrEqual => matches!(
+                self.compare(found),
+                Some(Ordering::Greater) | Some(Ordering::Equal)
+            ),
+            Operator::Less => self.compare(found) == Some(Ordering::Less),
+            Operator::LessOrEqual => matches!(
+                self.compare(found),
+                Some(Ordering::Less) | Some(Ordering::Equal)
+            ),
+        }
+    }
+
+    // Numbers are compared numerically and everything else as strings, which orders ISO 8601
+    // dates and timestamps chronologically. Missing and null values never satisfy a comparison.
+    fn compare(&self, found: Option<&Value>) -> Option<Ordering> {
+        match found {
+            None | Some(Value::Null) => None,
+            Some(value) => {
+                let actual = cell(Some(value));
+
+                match (actual.parse::<f64>(), self.value.parse::<f64>()) {
+                    (Ok(a), Ok(b)) => a.partial_cmp(&b),
+                    _ => Some(actual.as_str().cmp(self.value.as_str())),
+                }
+            }
+        }
+    }
+}
+
+pub fn args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("fields")
+            .long("fields")
+            .takes_value(true)
+            .help("Comma separated attribute paths to keep in each result"),
+        Arg::with_name("where")
+            .long("where")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .help("Only keeps results matching path=value, !=, ~ (contains), >, >=, < or <="),
+    ]
+}
+
+// The --fields and --where options of a list command, applied client side to its results
+pub struct Filter {
+    fields: Option<Vec<String>>,
+    predicates: Vec<Predicate>,
+}
+
+impl Filter {
+    pub fn from_matches(matches: &ArgMatches) -> Result<Filter, CLIError> {
+        let fields = matches
+            .value_of("fields")
+            .map(|fields| fields.split(',').map(|f| f.trim().to_string()).collect());
+
+        let predicates = matches
+            .values_of("where")
+            .map(|values| values.map(Predicate::from_str).collect())
+            .unwrap_or_else(|| Ok(vec![]))?;
+
+        Ok(Filter { fields, predicates })
+    }
+
+    pub fn apply(&self, output: Output) -> Output {
+        match output {
+            Output::Json(mut value) => {
+                if let Some(Value::Array(items)) = value.get_mut("data") {
+                    let kept = items
+                        .drain(..)
+                        .filter(|item| self.predicates.iter().all(|p| p.matches(item)))
+                        .map(|item| self.project(item))
+                        .collect();
+                    *items = kept;
+                }
+
+                Output::Json(value)
+            }
+            message => message,
+        }
+    }
+
+    // Rebuilds the record with only the requested fields, keeping their nesting
+    fn project(&self, record: Value) -> Value {
+        let fields = match self.fields {
+            Some(ref fields) => fields,
+            None => return record,
+        };
+
+        let mut projected = Value::Object(Map::new());
+
+        for field in fields {
+            if let Some(value) = lookup(&record, field.as_str()) {
+                insert(&mut projected, field.as_str(), value.clone());
+            }
+        }
+
+        projected
+    }
+}
+
+fn insert(target: &mut Value, path: &str, value: Value) {
+    let mut segments = path.split('.').peekable();
+    let mut current = target;
+
+    while let Some(segment) = segments.next() {
+        let map = match current {
+            Value::Object(map) => map,
+            _ => return,
+        };
+
+        if segments.peek().is_none() {
+            map.insert(segment.to_string(), value);
+            return;
+        }
+
+        current = map
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> Value {
+        serde_json::json!({
+            "id": "episode",
+            "attributes": {
+                "title": "The Pilot",
+                "ordinal": 9,
+                "premiered_on": "2020-03-01",
+                "tags": ["drama", "history"],
+                "description": null
+            }
+        })
+    }
+
+    fn matches(predicate: &str) -> bool {
+        predicate.parse::<Predicate>().unwrap().matches(&record())
+    }
+
+    #[test]
+    fn parses_each_operator() {
+        let cases = [
+            ("a=1", Operator::Equal),
+            ("a!=1", Operator::NotEqual),
+            ("a~1", Operator::Contains),
+            ("a>1", Operator::Greater),
+            ("a>=1", Operator::GreaterOrEqual),
+            ("a<1", Operator::Less),
+            ("a<=1", Operator::LessOrEqual),
+        ];
+
+        for (expression, operator) in cases.iter() {
+            let predicate: Predicate = expression.parse().unwrap();
+
+            assert_eq!(predicate.path, "a");
+            assert_eq!(predicate.operator, *operator);
+            assert_eq!(predicate.value, "1");
+        }
+    }
+
+    #[test]
+    fn parses_at_the_first_operator() {
+        let predicate: Predicate = " attributes.title = a=b ".parse().unwrap();
+
+        assert_eq!(predicate.path, "attributes.title");
+        assert_eq!(predicate.operator, Operator::Equal);
+        assert_eq!(predicate.value, "a=b");
+    }
+
+    #[test]
+    fn rejects_malformed_predicates() {
+        for expression in ["attributes.title", "=value", " >= 2", ""].iter() {
+            match expression.parse::<Predicate>() {
+                Err(CLIError::InvalidFilter(ref filter)) => assert_eq!(filter, expression),
+                other => panic!("Expected InvalidFilter error but recieved {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn equality() {
+        assert!(matches("attributes.title=The Pilot"));
+        assert!(!matches("attributes.title=Pilot"));
+        assert!(matches("attributes.title!=Pilot"));
+        assert!(matches("attributes.ordinal=9"));
+        assert!(matches("attributes.missing="));
+    }
+
+    #[test]
+    fn contains() {
+        assert!(matches("attributes.title~Pilot"));
+        assert!(!matches("attributes.title~pilot"));
+        assert!(matches("attributes.tags~drama"));
+        assert!(!matches("attributes.tags~dram"));
+    }
+
+    #[test]
+    fn compares_numbers_numerically() {
+        assert!(!matches("attributes.ordinal>10"));
+        assert!(matches("attributes.ordinal<10"));
+        assert!(matches("attributes.ordinal>=9"));
+        assert!(matches("attributes.ordinal<=9"));
+        assert!(!matches("attributes.ordinal>9"));
+    }
+
+    #[test]
+    fn compares_strings_lexically() {
+        assert!(matches("attributes.premiered_on>=2020-01-01"));
+        assert!(matches("attributes.premiered_on<2020-10-01"));
+        assert!(!matches("attributes.premiered_on>2020-03-01"));
+        assert!(matches("attributes.title>The Cat"));
+    }
+
+    #[test]
+    fn missing_and_null_values_never_compare() {
+        for operator in [">", ">=", "<", "<="].iter() {
+            assert!(!matches(
+                format!("attributes.missing{}0", operator).as_str()
+            ));
+            assert!(!matches(
+                format!("attributes.description{}0", operator).as_str()
+            ));
+        }
+    }
+}
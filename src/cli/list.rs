@@ -0,0 +1,140 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use mm_client::{Client, Endpoints};
+
+use std::str::FromStr;
+
+use crate::error::CLIError;
+use crate::filter::{self, Filter};
+use crate::output::Output;
+
+pub fn list_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("list")
+        .about("Lists objects of the requested type")
+        .arg(
+            Arg::with_name("type")
+                .required(true)
+                .help("Object type to list"),
+        )
+        .args(&page_args())
+        .args(&filter::args())
+}
+
+pub fn children_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("children")
+        .about("Lists objects of the requested type that belong to a parent object")
+        .arg(
+            Arg::with_name("parent-type")
+                .required(true)
+                .help("Object type of the parent"),
+        )
+        .arg(
+            Arg::with_name("parent-id")
+                .required(true)
+                .help("Object id of the parent"),
+        )
+        .arg(
+            Arg::with_name("type")
+                .required(true)
+                .help("Object type to list"),
+        )
+        .args(&page_args())
+        .args(&filter::args())
+}
+
+fn page_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("param")
+            .long("param")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .help("Query parameter to pass to the API in key=value form"),
+        Arg::with_name("page")
+            .long("page")
+            .takes_value(true)
+            .help("Page of results to request"),
+        Arg::with_name("page-size")
+            .long("page-size")
+            .takes_value(true)
+            .help("Number of results to request per page"),
+        Arg::with_name("all")
+            .long("all")
+            .takes_value(false)
+            .conflicts_with("page")
+            .help("Requests every page of results"),
+    ]
+}
+
+pub fn run_list(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let endpoint = parse_endpoint(matches.value_of("type"))?;
+    let owned = params(matches)?;
+    let params = borrow_params(&owned);
+    let filter = Filter::from_matches(matches)?;
+
+    let output = if matches.is_present("all") {
+        format_all(client.list_all(endpoint, params)?)
+    } else {
+        crate::handle_client_response(client.list(endpoint, params))?
+    };
+
+    Ok(filter.apply(output))
+}
+
+pub fn run_children(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let parent = parse_endpoint(matches.value_of("parent-type"))?;
+    let parent_id = matches.value_of("parent-id").unwrap_or_default();
+    let endpoint = parse_endpoint(matches.value_of("type"))?;
+    let owned = params(matches)?;
+    let params = borrow_params(&owned);
+    let filter = Filter::from_matches(matches)?;
+
+    let output = if matches.is_present("all") {
+        format_all(client.child_list_all(endpoint, parent_id, parent, Some(params))?)
+    } else {
+        crate::handle_client_response(client.child_list(endpoint, parent_id, parent, Some(params)))?
+    };
+
+    Ok(filter.apply(output))
+}
+
+pub fn parse_endpoint(value: Option<&str>) -> Result<Endpoints, CLIError> {
+    Endpoints::from_str(value.unwrap_or_default()).or(Err(CLIError::Endpoint))
+}
+
+// Collects the query parameters requested through the --param, --page and --page-size flags
+fn params(matches: &ArgMatches) -> Result<Vec<(String, String)>, CLIError> {
+    let mut params = matches
+        .values_of("param")
+        .map(|values| values.map(parse_param).collect::<Result<Vec<_>, _>>())
+        .unwrap_or_else(|| Ok(vec![]))?;
+
+    if let Some(page) = matches.value_of("page") {
+        params.push(("page".to_string(), page.to_string()));
+    }
+
+    if let Some(size) = matches.value_of("page-size") {
+        params.push(("page-size".to_string(), size.to_string()));
+    }
+
+    Ok(params)
+}
+
+fn parse_param(param: &str) -> Result<(String, String), CLIError> {
+    let mut parts = param.splitn(2, '=');
+
+    match (parts.next(), parts.next()) {
+        (Some(name), Some(value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(CLIError::InvalidParam(param.to_string())),
+    }
+}
+
+fn borrow_params(params: &[(String, String)]) -> Vec<(&str, &str)> {
+    params
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect()
+}
+
+fn format_all(items: Vec<serde_json::Value>) -> Output {
+    Output::Json(serde_json::json!({ "data": items }))
+}
@@ -9,10 +9,175 @@
 //! Configuration for the cli is contained within a `config.toml` file. It uses a `live` key to
 //! designate your credentials for the production API and a `staging` key for the staging API.
 //!
-//! The confiuration file can be created manually or the cli can generate it with some assistance.
-//! If you run the cli without a configuration file then it will attempt to generate one and prompt
-//! for the necessary credentials. Settings can be rewritten by running the cli with the `init`
-//! flag.
+//! Credentials can also be supplied through the `MM_KEY` and `MM_SECRET` environment variables
+//! for the production API and `MM_STAGING_KEY` and `MM_STAGING_SECRET` for the staging API.
+//! Environment variables take precedence over `config.toml`, which allows the cli to be run
+//! without writing secrets to disk.
+//!
+//! The confiuration file can be created manually or managed through the `profile` subcommands.
+//!
+//! ### Fetching objects
+//!
+//! `mm_cli get <type> <id>`, or the shorter `mm_cli <type> <id>`, fetches a single object.
+//!
+//! ### Listing objects
+//!
+//! `mm_cli list <type>` lists objects of a type and `mm_cli children <parent-type> <parent-id>
+//! <type>` lists the objects of a type that belong to a parent object. Query parameters can be
+//! passed with `--param key=value`, pages selected with `--page` and `--page-size`, and every
+//! page of results requested at once with `--all`.
+//!
+//! List results can be narrowed down without leaving the command line. `--fields` keeps only the
+//! given comma separated attribute paths of each result, and `--where` keeps only the results that
+//! match a predicate. Predicates take the form `path<op>value`, where `<op>` is one of `=`, `!=`,
+//! `~` (contains), `>`, `>=`, `<` or `<=`. Comparisons are numeric for numbers and otherwise
+//! compare strings, which orders ISO 8601 dates correctly. `--where` can be repeated and every
+//! predicate must match. Both are applied to the combined results when used with `--all`.
+//!
+//! ```text
+//! mm_cli list shows --all --where attributes.premiered_on>=2020-01-01 --where attributes.title~News \
+//!     --fields id,attributes.title,attributes.slug
+//! ```
+//!
+//! ### Modifying objects
+//!
+//! `mm_cli create <parent-type> <parent-id> <type>` and `mm_cli update <type> <id>` send a JSON
+//! request body read from the file given by `--file`, or from stdin when no file is given.
+//! `mm_cli delete <type> <id>` and `mm_cli move <type> <id> <parent-type> <parent-id>` ask for
+//! confirmation before making any change, which can be skipped with `--yes`.
+//!
+//! `mm_cli edit <type> <id>` fetches the edit object and opens it in `$VISUAL` or `$EDITOR`. Once
+//! the editor exits the edited JSON is validated and only the attributes that were changed are
+//! sent as an update. If any of those attributes were changed on the server while the editor was
+//! open the update is refused, unless `--force` is given.
+//!
+//! ### Batch changes
+//!
+//! `mm_cli batch <file>` applies a list of creates, updates, deletes and moves, running several at
+//! once (`--concurrency`, 4 by default). Each line of an NDJSON file is one operation, while a CSV
+//! file starts with a header naming the `operation`, `endpoint`, `id`, `parent`, `parent_id` and
+//! `body` columns, with the body given as JSON. Quoted CSV fields may span several lines. Files
+//! ending in `.csv` are read as CSV unless `--input-format` says otherwise.
+//!
+//! ```text
+//! {"operation":"update","endpoint":"show","id":"4a5b...","body":{"data":{"attributes":{}}}}
+//! {"operation":"move","endpoint":"episode","id":"6c7d...","parent":"season","parent_id":"8e9f..."}
+//! ```
+//!
+//! Every operation is attempted even when others fail, unless `--stop-on-error` is given. The
+//! outcome of each line is printed, or written as NDJSON to the file given by `--report`.
+//!
+//! ### Dry runs
+//!
+//! `--dry-run` makes `create`, `update`, `delete`, `move`, `edit` and `batch` print the requests
+//! they would send instead of sending them. Objects are still fetched where needed to build the
+//! requests, but nothing is changed and no confirmation is asked for.
+//!
+//! ```text
+//! mm_cli --dry-run delete episode 4a5b...
+//! ```
+//!
+//! ### Audit log
+//!
+//! `--audit-log <file>`, or the `MM_AUDIT_LOG` environment variable, appends a line of JSON to the
+//! file for every create, update, delete and move that is sent, recording the time, operator,
+//! request, response status and the id of the changed object. The operator is taken from
+//! `--operator` or `MM_OPERATOR`, falling back to `$USER`.
+//!
+//! ```text
+//! MM_AUDIT_LOG=~/mm_audit.jsonl mm_cli batch changes.ndjson
+//! ```
+//!
+//! ### Undoing changes
+//!
+//! `--journal <file>`, or the `MM_JOURNAL` environment variable, records the edit object of every
+//! object before it is updated or deleted by any command, including `batch`. `mm_cli undo` lists
+//! the entries in the journal, and `mm_cli undo <entry-id>` (or `--last` for the most recent
+//! entry) restores the attributes an update changed or recreates a deleted object beneath its
+//! parent. Undoing an update is itself recorded in the journal, so it can be undone in turn.
+//! Undoing a delete is not, as the object is recreated with a new id, and can not be undone.
+//!
+//! ```text
+//! export MM_JOURNAL=~/mm_journal.jsonl
+//! mm_cli delete episode 4a5b...
+//! mm_cli undo --last
+//! ```
+//!
+//! ### Interactive shell
+//!
+//! `mm_cli shell` opens an interactive session that keeps a single client for the duration of the
+//! session. Objects are entered with `cd <type>/<id>` (ids or slugs are accepted), and `ls`, `cat`,
+//! `edit` and `mv` act relative to the current object. `env live` and `env staging` switch between
+//! environments without leaving the session. Command history is kept beside `config.toml` and
+//! commands and object types can be completed with tab. Type `help` for the full list of commands.
+//!
+//! ```text
+//! mm:live:/> cd show/my-show-slug
+//! mm:live:/shows/4a5b...> ls seasons
+//! ```
+//!
+//! ### Browsing in the terminal
+//!
+//! `mm_cli tui` opens a keyboard driven browser that starts from the list of franchises and
+//! descends through shows, seasons and episodes to their assets. A show lists its specials after
+//! its seasons, and specials open onto their assets too. The selected object's attributes,
+//! availability windows and images are shown beside the list. Use the arrow keys to move, enter to
+//! open the selected object, backspace to go back, `/` to search the loaded list by title and `q`
+//! to quit.
+//!
+//! ### Following changes
+//!
+//! `mm_cli changelog` lists the changes made since the RFC 3339 timestamp given by `--since`, or in
+//! the last 24 hours when it is omitted, and can be narrowed down to a single resource type with
+//! `--type` and a single action with `--action`. With `--follow` the changelog is polled every
+//! `--interval` seconds (30 by default) and new changes are printed as they appear, one JSON object
+//! per line, starting from now unless `--since` is given. Polls that fail because the API can not
+//! be reached or has a server error are reported on stderr and retried after a growing delay.
+//! `--summary` prints a readable line per change instead.
+//!
+//! ```text
+//! mm_cli changelog --since 2020-01-01T00:00:00Z --type asset --follow --summary
+//! ```
+//!
+//! ### Expiring content
+//!
+//! `mm_cli report expiring` lists the assets whose public, all members or station members window
+//! ends within the next `--days` days (7 by default), along with assets that have no public window
+//! at all. Entries are grouped by show and season. Every show is crawled unless `--show` is given
+//! one or more times. Use `--output csv` or `--output table` for a list that can be shared. Shows
+//! that can not be fetched are reported on stderr, and under `failures` in JSON output, while the
+//! rest of the report is still produced.
+//!
+//! ```text
+//! mm_cli report expiring --days 14 --output table
+//! ```
+//!
+//! ### Output formats
+//!
+//! Results are pretty printed as JSON by default. The `--output` flag selects between `pretty`,
+//! `json`, `ndjson` (one resource per line), `csv`, `table` and `yaml` output. The columns shown in
+//! `csv` and `table` output can be chosen with `--columns`, which takes a comma separated list of
+//! attribute paths such as `id,attributes.title,attributes.slug`.
+//!
+//! ### Shell completions
+//!
+//! `mm_cli completions <shell>` prints a completion script for `bash`, `zsh` or `fish` that
+//! completes subcommands, flags, object types and the names of configured profiles. Profile names
+//! are looked up when completing, so the script does not need to be regenerated when profiles
+//! change. The zsh script can be sourced or saved as `_mm_cli` in a directory on `$fpath`.
+//!
+//! ```text
+//! source <(mm_cli completions bash)
+//! mm_cli completions zsh > ~/.zfunc/_mm_cli
+//! mm_cli completions fish > ~/.config/fish/completions/mm_cli.fish
+//! ```
+//!
+//! ### Profiles
+//!
+//! Additional named profiles can be stored in `config.toml` under the `profiles` key. Each profile
+//! has its own key/secret pair along with either an `environment` of `live` or `staging`, or a
+//! custom `base_url`. Profiles are selected with the `--profile` flag and can be managed with
+//! `mm_cli profile add <name>`, `mm_cli profile list` and `mm_cli profile remove <name>`.
 
 #![deny(missing_docs)]
 extern crate app_dirs;
@@ -22,149 +187,354 @@ extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde_json;
+extern crate serde_yaml;
 
-mod error;
+mod batch;
+mod changelog;
+mod completions;
 mod config;
+mod edit;
+mod error;
+mod filter;
+mod list;
+mod output;
+mod profile;
+mod report;
+mod shell;
+mod tui;
+mod undo;
+mod write;
 
-use app_dirs::{AppDataType, AppInfo, get_app_dir};
-use clap::{App, Arg};
+use app_dirs::{get_app_dir, AppDataType, AppInfo};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use mm_client::Client;
-use mm_client::Endpoints;
 use mm_client::MMCResult;
+use mm_client::{
+    ChainProvider, EnvVarProvider, Environment, FileJournal, JsonLinesAudit, MMCError,
+};
 
-use std::str::FromStr;
+use std::env;
+use std::ffi::OsString;
+use std::io;
+use std::sync::Arc;
 
-use crate::error::CLIError;
 use crate::config::Config;
+use crate::error::CLIError;
+use crate::output::{Output, Renderer};
 
 fn main() {
+    let matches = parse_args(env::args_os()).unwrap_or_else(|err| err.exit());
+
+    let info = AppInfo {
+        name: env!("CARGO_PKG_NAME"),
+        author: "tpt",
+    };
+
+    let config_path = get_app_dir(AppDataType::UserConfig, &info, "/")
+        .map(|mut path| {
+            path.push("config.toml");
+            path
+        })
+        .expect("Failed to run. Unable to determine default config location.");
+
+    let path = config_path
+        .to_str()
+        .expect("Failed to parse config location.");
+
+    let result = match matches.subcommand() {
+        ("profile", Some(sub)) => profile::run(sub, path),
+        ("shell", Some(sub)) => shell::run(sub, path),
+        ("completions", Some(sub)) => completions::run(sub),
+        ("list", Some(sub)) => client(sub, path).and_then(|cl| list::run_list(sub, &cl)),
+        ("children", Some(sub)) => client(sub, path).and_then(|cl| list::run_children(sub, &cl)),
+        ("create", Some(sub)) => client(sub, path).and_then(|cl| write::run_create(sub, &cl)),
+        ("update", Some(sub)) => client(sub, path).and_then(|cl| write::run_update(sub, &cl)),
+        ("delete", Some(sub)) => client(sub, path).and_then(|cl| write::run_delete(sub, &cl)),
+        ("move", Some(sub)) => client(sub, path).and_then(|cl| write::run_move(sub, &cl)),
+        ("edit", Some(sub)) => client(sub, path).and_then(|cl| edit::run(sub, &cl)),
+        ("changelog", Some(sub)) => client(sub, path).and_then(|cl| changelog::run(sub, &cl)),
+        ("tui", Some(sub)) => client(sub, path).and_then(|cl| tui::run(sub, &cl)),
+        ("batch", Some(sub)) => client(sub, path).and_then(|cl| batch::run(sub, &cl)),
+        ("undo", Some(sub)) => client(sub, path).and_then(|cl| undo::run(sub, &cl)),
+        ("report", Some(sub)) => client(sub, path).and_then(|cl| report::run(sub, &cl)),
+        ("get", Some(sub)) => rq_get(sub, path, sub.value_of("type"), sub.value_of("id")),
+        // An object type given without an id is not rewritten by parse_args
+        (endpoint, Some(sub)) => {
+            let id = sub.values_of("").and_then(|mut values| values.next());
+            rq_get(&matches, path, Some(endpoint), id)
+        }
+        _ => return,
+    };
+
+    // Handle the result from the client, outputting it to the user
+    match result.and_then(|output| Renderer::from_matches(&matches)?.render(output)) {
+        Ok(ref value) if value.is_empty() => {}
+        Ok(ref value) => println!("{}", value),
+        Err(ref error) => println!("An error occured: {}", error),
+    };
+}
+
+// Parses the command line, supporting the original `mm_cli <type> <id>` form of fetching a single
+// object by rewriting it as `mm_cli get <type> <id>`. Clap passes everything after an external
+// subcommand through untouched, so without the rewrite global flags given after the id would be
+// silently ignored.
+fn parse_args<I, T>(args: I) -> Result<ArgMatches<'static>, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString>,
+{
+    let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+    let matches = app().get_matches_from_safe(args.clone())?;
+
+    let trailing = match matches.subcommand() {
+        (_, Some(sub)) if sub.is_present("") => sub.values_of_os("").map_or(0, Iterator::count),
+        _ => return Ok(matches),
+    };
+
+    // The external subcommand and its arguments are always the last of the arguments
+    let split = args.len() - trailing - 1;
+    let mut rewritten = args[..split].to_vec();
+    rewritten.push(OsString::from("get"));
+    rewritten.extend_from_slice(&args[split..]);
 
-    let matches = App::new(env!("CARGO_PKG_NAME"))
+    app().get_matches_from_safe(rewritten)
+}
+
+fn app<'a, 'b>() -> App<'a, 'b> {
+    App::new(env!("CARGO_PKG_NAME"))
         .author(env!("CARGO_PKG_AUTHORS"))
         .version(env!("CARGO_PKG_VERSION"))
+        .setting(AppSettings::AllowExternalSubcommands)
         .arg(
-            Arg::with_name("init")
-                .long("init")
+            Arg::with_name("staging")
+                .short("s")
+                .long("staging")
                 .takes_value(false)
-                .help("Creates/replaces a config.toml file"),
+                .global(true)
+                .help("Runs query against the staging environment"),
         )
         .arg(
-            Arg::with_name("type")
+            Arg::with_name("profile")
+                .short("p")
+                .long("profile")
                 .takes_value(true)
-                .required(false)
-                .help("Object type to query for"),
+                .global(true)
+                .conflicts_with("staging")
+                .help("Runs query using a named profile from config.toml"),
         )
         .arg(
-            Arg::with_name("id")
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .takes_value(false)
+                .global(true)
+                .help("Prints the requests that would change objects instead of sending them"),
+        )
+        .arg(
+            Arg::with_name("audit-log")
+                .long("audit-log")
                 .takes_value(true)
-                .required(false)
-                .help("Object id to query for"),
+                .global(true)
+                .env("MM_AUDIT_LOG")
+                .help("Appends a JSON line to this file for every change that is sent"),
         )
         .arg(
-            Arg::with_name("staging")
-                .short("s")
-                .long("staging")
-                .takes_value(false)
-                .help("Runs query against the staging environment"),
+            Arg::with_name("operator")
+                .long("operator")
+                .takes_value(true)
+                .global(true)
+                .env("MM_OPERATOR")
+                .help("Name recorded in the audit log as making changes, defaults to $USER"),
+        )
+        .arg(
+            Arg::with_name("journal")
+                .long("journal")
+                .takes_value(true)
+                .global(true)
+                .env("MM_JOURNAL")
+                .help("Records objects to this file before they are updated or deleted, for undo"),
         )
-        .get_matches();
+        .args(&output::args())
+        .subcommand(
+            SubCommand::with_name("get")
+                .about("Fetches a single object")
+                .arg(
+                    Arg::with_name("type")
+                        .required(true)
+                        .help("Object type to query for"),
+                )
+                .arg(
+                    Arg::with_name("id")
+                        .required(true)
+                        .help("Object id to query for"),
+                ),
+        )
+        .subcommand(profile::subcommand())
+        .subcommand(list::list_subcommand())
+        .subcommand(list::children_subcommand())
+        .subcommand(write::create_subcommand())
+        .subcommand(write::update_subcommand())
+        .subcommand(write::delete_subcommand())
+        .subcommand(write::move_subcommand())
+        .subcommand(edit::subcommand())
+        .subcommand(batch::subcommand())
+        .subcommand(undo::subcommand())
+        .subcommand(changelog::subcommand())
+        .subcommand(report::subcommand())
+        .subcommand(shell::subcommand())
+        .subcommand(tui::subcommand())
+        .subcommand(completions::subcommand())
+}
 
-    let info = AppInfo {
-        name: env!("CARGO_PKG_NAME"),
-        author: "tpt",
-    };
+fn rq_get(
+    matches: &ArgMatches,
+    path: &str,
+    endpoint: Option<&str>,
+    id: Option<&str>,
+) -> Result<Output, CLIError> {
+    let endpoint = list::parse_endpoint(endpoint)?;
+    let id = id.ok_or(CLIError::MissingId)?;
 
-    let config_path = get_app_dir(AppDataType::UserConfig, &info, "/")
-        .and_then(|mut path| {
-            path.push("config.toml");
-            Ok(path)
+    client(matches, path).and_then(|cl| handle_client_response(cl.get(endpoint, id, None)))
+}
+
+// Builds a client from the requested profile, or from the live/staging credentials found in the
+// environment or config.toml
+fn client(matches: &ArgMatches, path: &str) -> Result<Client, CLIError> {
+    let client = if let Some(name) = matches.value_of("profile") {
+        Config::parse_config(path).and_then(|config| {
+            config
+                .profile(name)
+                .and_then(|profile| profile.client().map_err(CLIError::Network))
         })
-        .expect(
-            "Failed to run. Unable to determine default config location.",
-        );
+    } else if matches.is_present("staging") {
+        environment_client(path, Environment::Staging)
+    } else {
+        environment_client(path, Environment::Live)
+    };
 
-    let path = config_path.to_str().expect(
-        "Failed to parse config location.",
-    );
+    ClientOptions::from_matches(matches).apply(client?)
+}
 
-    if matches.is_present("init") {
-        Config::create(path);
-    };
+// The global flags that configure a client regardless of the credentials it was built from, kept
+// so that clients rebuilt later in a session are configured the same way
+#[derive(Clone, Debug)]
+struct ClientOptions {
+    dry_run: bool,
+    journal: Option<String>,
+    audit_log: Option<String>,
+    operator: String,
+}
 
-    match (matches.value_of("type"), matches.value_of("id")) {
-        (Some(endpoint), Some(id)) => {
+impl ClientOptions {
+    fn from_matches(matches: &ArgMatches) -> ClientOptions {
+        ClientOptions {
+            dry_run: matches.is_present("dry-run"),
+            journal: matches.value_of("journal").map(String::from),
+            audit_log: matches.value_of("audit-log").map(String::from),
+            operator: matches
+                .value_of("operator")
+                .map(String::from)
+                .or_else(|| env::var("USER").ok())
+                .unwrap_or_else(|| "unknown".to_string()),
+        }
+    }
 
-            Config::parse_config(path).and_then(|config| {
-                let result = Endpoints::from_str(endpoint)
-                    .or(Err(CLIError::Endpoint))
-                    .and_then(|ep| {
-                        rq_endpoint(&config, matches.is_present("staging"), ep, id)
-                    });
+    fn apply(&self, client: Client) -> Result<Client, CLIError> {
+        let mut client = client.dry_run(self.dry_run);
 
-                // Handle the result from the client, outputting it to the user
-                match result {
-                    Ok(ref value) => println!("{}", value),
-                    Err(ref error) => println!("An error occured: {}", error),
-                };
+        if let Some(ref journal) = self.journal {
+            client = client.with_journal(Arc::new(FileJournal::new(journal)));
+        }
 
-                result
-            });
+        match self.audit_log {
+            Some(ref audit_log) => {
+                let audit = JsonLinesAudit::open(audit_log).map_err(CLIError::Network)?;
+                Ok(client.with_audit(Arc::new(audit), self.operator.as_str()))
+            }
+            None => Ok(client),
         }
-        _ => (),
     }
 }
 
-fn rq_endpoint(
-    config: &Config,
-    is_staging: bool,
-    endpoint: Endpoints,
-    id: &str,
-) -> Result<String, CLIError> {
-    let conf = if is_staging {
-        &config.staging
-    } else {
-        &config.live
-    };
-
-    // Attempt to get a response from the requested endpoint if it is
-    // one of the available endpoints
-    match *conf {
-        Some(ref key_sec) => {
+// Builds a client for the environment from the credentials in the environment or config.toml
+fn environment_client(path: &str, environment: Environment) -> Result<Client, CLIError> {
+    Client::from_provider(&credential_provider(path)?, environment).map_err(|err| match err {
+        MMCError::MissingCredentials(_) => CLIError::EndpointConfigMissing,
+        err => CLIError::Network(err),
+    })
+}
 
-            let client = if is_staging {
-                Client::staging(key_sec.key.as_str(), key_sec.secret.as_str())
-            } else {
-                Client::new(key_sec.key.as_str(), key_sec.secret.as_str())
-            };
+// Looks up credentials from the environment first, falling back to config.toml. A missing
+// config.toml only leaves the environment to look in, while one that can not be read or parsed
+// is reported.
+fn credential_provider(path: &str) -> Result<ChainProvider, CLIError> {
+    let provider = ChainProvider::new().with(EnvVarProvider::new());
 
-            client.map_err(CLIError::Network).and_then(|cl| {
-                handle_client_response(cl.get(endpoint, id, None))
-            })
+    match Config::parse_config(path) {
+        Ok(config) => Ok(provider.with(config)),
+        Err(CLIError::ConfigStorageFailure(ref err)) if err.kind() == io::ErrorKind::NotFound => {
+            Ok(provider)
         }
-        None => Err(CLIError::EndpointConfigMissing),
+        Err(err) => Err(err),
     }
 }
 
+// Looks up the value of a global argument from the most deeply nested subcommand it was
+// supplied to
+fn global_value<'a>(matches: &'a ArgMatches, name: &str) -> Option<&'a str> {
+    matches
+        .subcommand()
+        .1
+        .and_then(|sub| global_value(sub, name))
+        .or_else(|| matches.value_of(name))
+}
+
 /// Handles responses from the Core Data Client and transforms them into
 /// a Result that is ready for output to a user
-fn handle_client_response(result: MMCResult<String>) -> Result<String, CLIError> {
+fn handle_client_response(result: MMCResult<String>) -> Result<Output, CLIError> {
     match result {
-        Ok(json_string) => to_pretty_print(json_string),
+        Ok(json_string) => serde_json::from_str(json_string.as_str())
+            .map(Output::Json)
+            .map_err(CLIError::Format),
         Err(err) => Err(CLIError::Network(err)),
     }
 }
 
-// Accepts a String of json, and returns the Result of attempting to transform
-// it into a pretty printable String
-fn to_pretty_print(json_string: String) -> Result<String, CLIError> {
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Attempt to deserialize the json string
-    let parsed: Result<serde_json::Value, serde_json::Error> =
-        serde_json::from_str(json_string.as_str());
+    #[test]
+    fn legacy_get_parses_trailing_global_flags() {
+        let matches = parse_args(vec!["mm_cli", "show", "abc", "--staging"]).unwrap();
+
+        match matches.subcommand() {
+            ("get", Some(sub)) => {
+                assert_eq!(sub.value_of("type"), Some("show"));
+                assert_eq!(sub.value_of("id"), Some("abc"));
+                assert!(sub.is_present("staging"));
+            }
+            (name, _) => panic!("Expected get subcommand but recieved {:?}", name),
+        }
+    }
+
+    #[test]
+    fn legacy_get_keeps_leading_global_flags() {
+        let matches =
+            parse_args(vec!["mm_cli", "-p", "show", "show", "abc", "-o", "json"]).unwrap();
+
+        match matches.subcommand() {
+            ("get", Some(sub)) => {
+                assert_eq!(sub.value_of("profile"), Some("show"));
+                assert_eq!(sub.value_of("output"), Some("json"));
+                assert_eq!(sub.value_of("id"), Some("abc"));
+            }
+            (name, _) => panic!("Expected get subcommand but recieved {:?}", name),
+        }
+    }
 
-    // Return either successfully decoded json or a Format error
-    match parsed {
-        Ok(json) => serde_json::ser::to_string_pretty(&json).map_err(CLIError::Format),
-        Err(err) => Err(CLIError::Format(err)),
+    #[test]
+    fn legacy_get_rejects_unknown_flags() {
+        assert!(parse_args(vec!["mm_cli", "show", "abc", "--stagign"]).is_err());
     }
 }
@@ -0,0 +1,290 @@
+use clap::{Arg, ArgMatches};
+use serde_json::Value;
+
+use std::str::FromStr;
+
+use crate::batch::REPORT_COLUMNS;
+use crate::error::CLIError;
+use crate::report::EXPIRING_COLUMNS;
+use crate::undo::JOURNAL_COLUMNS;
+
+pub const FORMATS: [&str; 6] = ["pretty", "json", "ndjson", "csv", "table", "yaml"];
+
+pub const DEFAULT_COLUMNS: [&str; 3] = ["id", "type", "attributes.title"];
+
+// The result of a command, either JSON from the API or a message for the user
+pub enum Output {
+    Json(Value),
+    Message(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Pretty,
+    Json,
+    Ndjson,
+    Csv,
+    Table,
+    Yaml,
+}
+
+impl FromStr for Format {
+    type Err = CLIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(Format::Pretty),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            "csv" => Ok(Format::Csv),
+            "table" => Ok(Format::Table),
+            "yaml" => Ok(Format::Yaml),
+            x => Err(CLIError::UnknownFormat(x.to_string())),
+        }
+    }
+}
+
+pub fn args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .takes_value(true)
+            .global(true)
+            .possible_values(&FORMATS)
+            .help("Output format, defaults to pretty printed JSON"),
+        Arg::with_name("columns")
+            .long("columns")
+            .takes_value(true)
+            .global(true)
+            .help("Comma separated attribute paths to show in csv and table output"),
+    ]
+}
+
+pub struct Renderer {
+    format: Format,
+    columns: Vec<String>,
+}
+
+impl Renderer {
+    pub fn new(format: Format, columns: &[&str]) -> Renderer {
+        Renderer {
+            format,
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    pub fn from_matches(matches: &ArgMatches) -> Result<Renderer, CLIError> {
+        let format = crate::global_value(matches, "output")
+            .map(Format::from_str)
+            .unwrap_or(Ok(Format::Pretty))?;
+
+        // Projected --fields make sensible columns when none were requested explicitly
+        let columns = crate::global_value(matches, "columns")
+            .or_else(|| crate::global_value(matches, "fields"))
+            .map(|columns| columns.split(',').map(|c| c.trim().to_string()).collect())
+            .unwrap_or_else(|| {
+                let defaults: &[&str] = match matches.subcommand_name() {
+                    Some("batch") => &REPORT_COLUMNS,
+                    Some("undo") => &JOURNAL_COLUMNS,
+                    Some("report") => &EXPIRING_COLUMNS,
+                    _ => &DEFAULT_COLUMNS,
+                };
+
+                defaults.iter().map(|c| c.to_string()).collect()
+            });
+
+        Ok(Renderer { format, columns })
+    }
+
+    pub fn render(&self, output: Output) -> Result<String, CLIError> {
+        let value = match output {
+            Output::Json(value) => value,
+            Output::Message(message) => return Ok(message),
+        };
+
+        match self.format {
+            Format::Pretty => serde_json::to_string_pretty(&value).map_err(CLIError::Format),
+            Format::Json => serde_json::to_string(&value).map_err(CLIError::Format),
+            Format::Ndjson => records(&value)
+                .iter()
+                .map(|record| serde_json::to_string(record).map_err(CLIError::Format))
+                .collect::<Result<Vec<String>, CLIError>>()
+                .map(|lines| lines.join("\n")),
+            Format::Csv => Ok(self.csv(&value)),
+            Format::Table => Ok(self.table(&value)),
+            Format::Yaml => serde_yaml::to_string(&value).map_err(CLIError::Yaml),
+        }
+    }
+
+    fn rows(&self, value: &Value) -> Vec<Vec<String>> {
+        records(value)
+            .iter()
+            .map(|record| {
+                self.columns
+                    .iter()
+                    .map(|column| cell(lookup(record, column.as_str())))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn csv(&self, value: &Value) -> String {
+        let mut lines = vec![csv_line(&self.columns)];
+        lines.extend(self.rows(value).iter().map(|row| csv_line(row)));
+        lines.join("\n")
+    }
+
+    fn table(&self, value: &Value) -> String {
+        let mut rows = vec![self.columns.clone()];
+        rows.extend(self.rows(value));
+
+        let widths: Vec<usize> = (0..self.columns.len())
+            .map(|i| {
+                rows.iter()
+                    .map(|row| row[i].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        rows.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(widths.iter())
+                    .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                    .collect::<Vec<String>>()
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+// Returns the resources contained in an API response. List responses yield each entry of their
+// data array, while single object responses yield their data object.
+pub fn records(value: &Value) -> Vec<&Value> {
+    match value.get("data") {
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(item) => vec![item],
+        None => vec![value],
+    }
+}
+
+// Follows a dot separated path of object keys and array indexes into a value
+pub fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |current, segment| match current {
+            Value::Array(items) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| items.get(index)),
+            _ => current.get(segment),
+        })
+}
+
+pub fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.to_string(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn csv_line(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|cell| {
+            if cell.contains(&[',', '"', '\n', '\r'][..]) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(format: Format, columns: &[&str], value: Value) -> String {
+        Renderer::new(format, columns)
+            .render(Output::Json(value))
+            .unwrap()
+    }
+
+    #[test]
+    fn csv_escapes_quotes_commas_and_newlines() {
+        let value = serde_json::json!({
+            "data": [
+                {"id": "a", "attributes": {"title": "Plain"}},
+                {"id": "b", "attributes": {"title": "Say \"hello\""}},
+                {"id": "c", "attributes": {"title": "One, two"}},
+                {"id": "d", "attributes": {"title": "First\nsecond"}}
+            ]
+        });
+
+        assert_eq!(
+            render(Format::Csv, &["id", "attributes.title"], value),
+            "id,attributes.title\n\
+             a,Plain\n\
+             b,\"Say \"\"hello\"\"\"\n\
+             c,\"One, two\"\n\
+             d,\"First\nsecond\""
+        );
+    }
+
+    #[test]
+    fn csv_leaves_missing_columns_empty() {
+        let value = serde_json::json!({
+            "data": [
+                {"id": "a", "attributes": {"title": null}},
+                {"id": "b"}
+            ]
+        });
+
+        assert_eq!(
+            render(Format::Csv, &["id", "attributes.title", "type"], value),
+            "id,attributes.title,type\na,,\nb,,"
+        );
+    }
+
+    #[test]
+    fn table_pads_columns_and_leaves_missing_columns_empty() {
+        let value = serde_json::json!({
+            "data": {"id": "episode", "attributes": {"tags": ["drama"]}}
+        });
+
+        assert_eq!(
+            render(
+                Format::Table,
+                &["id", "attributes.title", "attributes.tags.0"],
+                value
+            ),
+            "id       attributes.title  attributes.tags.0\nepisode                    drama"
+        );
+    }
+
+    #[test]
+    fn ndjson_prints_one_record_per_line() {
+        let value = serde_json::json!({"data": [{"id": "a"}, {"id": "b"}]});
+
+        assert_eq!(
+            render(Format::Ndjson, &[], value),
+            "{\"id\":\"a\"}\n{\"id\":\"b\"}"
+        );
+    }
+
+    #[test]
+    fn messages_are_not_formatted() {
+        let rendered = Renderer::new(Format::Csv, &DEFAULT_COLUMNS)
+            .render(Output::Message("Done".to_string()))
+            .unwrap();
+
+        assert_eq!(rendered, "Done");
+    }
+}
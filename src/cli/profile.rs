@@ -0,0 +1,117 @@
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use mm_client::Environment;
+
+use std::str::FromStr;
+
+use crate::config::{Config, Profile};
+use crate::error::CLIError;
+use crate::output::Output;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("profile")
+        .about("Manages the named key/secret profiles stored in config.toml")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("add")
+                .about("Adds or replaces a profile, prompting for any missing credentials")
+                .arg(
+                    Arg::with_name("name")
+                        .required(true)
+                        .help("Name of the profile"),
+                )
+                .arg(
+                    Arg::with_name("key")
+                        .long("key")
+                        .takes_value(true)
+                        .help("API key for the profile"),
+                )
+                .arg(
+                    Arg::with_name("secret")
+                        .long("secret")
+                        .takes_value(true)
+                        .help("API secret for the profile"),
+                )
+                .arg(
+                    Arg::with_name("environment")
+                        .long("environment")
+                        .takes_value(true)
+                        .possible_values(&["live", "staging"])
+                        .default_value("live")
+                        .help("Environment the profile queries against"),
+                )
+                .arg(
+                    Arg::with_name("base-url")
+                        .long("base-url")
+                        .takes_value(true)
+                        .help("Custom API base url, overriding the environment"),
+                ),
+        )
+        .subcommand(SubCommand::with_name("list").about("Lists the configured profiles"))
+        .subcommand(
+            SubCommand::with_name("remove")
+                .about("Removes a profile")
+                .arg(
+                    Arg::with_name("name")
+                        .required(true)
+                        .help("Name of the profile"),
+                ),
+        )
+}
+
+pub fn run(matches: &ArgMatches, path: &str) -> Result<Output, CLIError> {
+    match matches.subcommand() {
+        ("add", Some(sub)) => add(sub, path),
+        ("list", Some(_)) => list(path),
+        ("remove", Some(sub)) => remove(sub, path),
+        _ => Ok(Output::Message(String::new())),
+    }
+}
+
+fn add(matches: &ArgMatches, path: &str) -> Result<Output, CLIError> {
+    let mut config = Config::load_or_default(path)?;
+    let name = matches.value_of("name").unwrap_or_default();
+
+    let profile = Profile {
+        key: matches
+            .value_of("key")
+            .map(String::from)
+            .unwrap_or_else(|| Config::prompt_for_input("API Key: ")),
+        secret: matches
+            .value_of("secret")
+            .map(String::from)
+            .unwrap_or_else(|| Config::prompt_for_input("API Secret: ")),
+        environment: Environment::from_str(matches.value_of("environment").unwrap_or("live"))?,
+        base_url: matches.value_of("base-url").map(String::from),
+    };
+
+    config.profiles.insert(name.to_string(), profile);
+    config.store(path)?;
+
+    Ok(Output::Message(format!("Stored profile {}", name)))
+}
+
+fn list(path: &str) -> Result<Output, CLIError> {
+    let config = Config::load_or_default(path)?;
+
+    Ok(Output::Message(
+        config
+            .profiles
+            .iter()
+            .map(|(name, profile)| format!("{}\t{}\t{}", name, profile.target(), profile.key))
+            .collect::<Vec<String>>()
+            .join("\n"),
+    ))
+}
+
+fn remove(matches: &ArgMatches, path: &str) -> Result<Output, CLIError> {
+    let mut config = Config::load_or_default(path)?;
+    let name = matches.value_of("name").unwrap_or_default();
+
+    config
+        .profiles
+        .remove(name)
+        .ok_or_else(|| CLIError::UnknownProfile(name.to_string()))?;
+    config.store(path)?;
+
+    Ok(Output::Message(format!("Removed profile {}", name)))
+}
@@ -0,0 +1,79 @@
+use chrono::Utc;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use mm_client::Client;
+
+use crate::error::CLIError;
+use crate::output::Output;
+
+// The columns shown for each expiring asset when listed as a table or CSV
+pub const EXPIRING_COLUMNS: [&str; 7] = [
+    "show", "season", "asset_id", "asset", "issue", "audience", "ends",
+];
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("report")
+        .about("Generates reports on the content of shows")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("expiring")
+                .about(
+                    "Lists assets whose public or member windows end within a number of days, \
+                     and assets without a public window",
+                )
+                .arg(
+                    Arg::with_name("days")
+                        .long("days")
+                        .takes_value(true)
+                        .default_value("7")
+                        .validator(|value| {
+                            value
+                                .parse::<u32>()
+                                .map(|_| ())
+                                .map_err(|_| "The days must be a whole number".to_string())
+                        })
+                        .help("Number of days ahead to look for windows ending"),
+                )
+                .arg(
+                    Arg::with_name("show")
+                        .long("show")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help("Id of a show to report on. May be repeated. Reports on every show if omitted"),
+                ),
+        )
+}
+
+pub fn run(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    match matches.subcommand() {
+        ("expiring", Some(sub)) => expiring(sub, client),
+        _ => Ok(Output::Message(String::new())),
+    }
+}
+
+fn expiring(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let days = matches
+        .value_of("days")
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(7);
+    let shows: Vec<&str> = matches
+        .values_of("show")
+        .map(|shows| shows.collect())
+        .unwrap_or_default();
+
+    let report = client.expiring_report(&shows, days, Utc::now())?;
+    let entries = serde_json::to_value(&report.entries).map_err(CLIError::Format)?;
+    let failures = serde_json::to_value(&report.failures).map_err(CLIError::Format)?;
+
+    // Failures are not part of table and CSV output, so are also noted
+    for failure in report.failures.iter() {
+        eprintln!(
+            "Unable to fully report on show {}. {}",
+            failure.show_id, failure.error
+        );
+    }
+
+    Ok(Output::Json(
+        serde_json::json!({ "data": entries, "failures": failures }),
+    ))
+}
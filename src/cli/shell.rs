@@ -0,0 +1,333 @@
+use clap::{App, ArgMatches, SubCommand};
+use mm_client::{Client, ConflictPolicy, Endpoints, Environment};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use serde_json::Value;
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::edit;
+use crate::error::CLIError;
+use crate::list::parse_endpoint;
+use crate::output::{Format, Output, Renderer, DEFAULT_COLUMNS};
+use crate::write;
+
+const COMMANDS: [&str; 10] = [
+    "cd", "ls", "cat", "edit", "mv", "pwd", "env", "help", "exit", "quit",
+];
+
+const HELP: &str = "\
+cd <type>/<id>                      Enters an object, such as cd show/my-show-slug
+cd ..                               Returns to the parent object
+cd                                  Returns to the top level
+ls [<type>]                         Lists the objects of a type belonging to the current object
+cat [<type>/<id>]                   Prints an object, defaulting to the current object
+edit [<type>/<id>]                  Edits an object in $EDITOR, defaulting to the current object
+mv <type>/<id> <type>/<id>          Moves an object to a new parent object
+pwd                                 Prints the current object path
+env [live|staging]                  Prints or switches the environment being queried
+exit                                Ends the session";
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("shell")
+        .about("Opens an interactive session for browsing and editing objects")
+}
+
+pub fn run(matches: &ArgMatches, path: &str) -> Result<Output, CLIError> {
+    let target = match matches.value_of("profile") {
+        Some(name) => name.to_string(),
+        None if matches.is_present("staging") => Environment::Staging.to_string(),
+        None => Environment::Live.to_string(),
+    };
+
+    let mut shell = Shell {
+        client: crate::client(matches, path)?,
+        options: crate::ClientOptions::from_matches(matches),
+        target,
+        config_path: path.to_string(),
+        location: vec![],
+    };
+
+    let mut editor = Editor::<ShellHelper>::new();
+    editor.set_helper(Some(ShellHelper));
+
+    let history = history_path(path);
+    let _ = editor.load_history(&history);
+
+    loop {
+        let line = match editor.readline(shell.prompt().as_str()) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(CLIError::Shell(err)),
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        editor.add_history_entry(line.as_str());
+
+        match shell.execute(line.as_str(), &mut editor) {
+            Ok(None) => break,
+            Ok(Some(ref message)) if message.is_empty() => {}
+            Ok(Some(message)) => println!("{}", message),
+            Err(err) => println!("An error occured: {}", err),
+        }
+    }
+
+    editor.save_history(&history).map_err(CLIError::Shell)?;
+
+    Ok(Output::Message(String::new()))
+}
+
+// Keeps the history beside config.toml
+fn history_path(config_path: &str) -> PathBuf {
+    Path::new(config_path).with_file_name("shell_history")
+}
+
+struct Shell {
+    client: Client,
+    options: crate::ClientOptions,
+    target: String,
+    config_path: String,
+    location: Vec<(Endpoints, String)>,
+}
+
+impl Shell {
+    fn prompt(&self) -> String {
+        format!("mm:{}:{}> ", self.target, self.pwd())
+    }
+
+    fn pwd(&self) -> String {
+        let path = self
+            .location
+            .iter()
+            .map(|(endpoint, id)| format!("{}/{}", endpoint, id))
+            .collect::<Vec<String>>()
+            .join("/");
+
+        format!("/{}", path)
+    }
+
+    // Runs a single line of input, returning the text to print or None when the session is over
+    fn execute(
+        &mut self,
+        line: &str,
+        editor: &mut Editor<ShellHelper>,
+    ) -> Result<Option<String>, CLIError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        let output = match words.as_slice() {
+            ["exit"] | ["quit"] => return Ok(None),
+            ["help"] => HELP.to_string(),
+            ["pwd"] => self.pwd(),
+            ["cd"] | ["cd", "/"] => {
+                self.location.clear();
+                String::new()
+            }
+            ["cd", ".."] => {
+                self.location.pop();
+                String::new()
+            }
+            ["cd", object] => self.cd(object)?,
+            ["ls"] => self.ls(None)?,
+            ["ls", endpoint] => self.ls(Some(endpoint))?,
+            ["cat"] => self.cat(self.current()?)?,
+            ["cat", object] => self.cat(parse_object(object)?)?,
+            ["edit"] => self.edit(self.current()?)?,
+            ["edit", object] => self.edit(parse_object(object)?)?,
+            ["mv", object, parent] => {
+                self.mv(parse_object(object)?, parse_object(parent)?, editor)?
+            }
+            ["env"] => self.target.clone(),
+            ["env", environment] => self.env(environment)?,
+            _ => return Err(CLIError::UnknownCommand(line.trim().to_string())),
+        };
+
+        Ok(Some(output))
+    }
+
+    fn current(&self) -> Result<(Endpoints, String), CLIError> {
+        self.location.last().cloned().ok_or(CLIError::MissingId)
+    }
+
+    // Fetches the object to make sure that it exists, and so that slugs are resolved to ids
+    fn cd(&mut self, object: &str) -> Result<String, CLIError> {
+        let (endpoint, id) = parse_object(object)?;
+        let response: Value = serde_json::from_str(
+            self.client
+                .get(endpoint.clone(), id.as_str(), None)?
+                .as_str(),
+        )?;
+
+        let id = response
+            .pointer("/data/id")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .unwrap_or(id);
+
+        self.location.push((endpoint, id));
+
+        Ok(String::new())
+    }
+
+    fn ls(&self, endpoint: Option<&str>) -> Result<String, CLIError> {
+        let parent = self.location.last();
+
+        let endpoint = match endpoint {
+            Some(endpoint) => parse_endpoint(Some(endpoint))?,
+            None => parent
+                .and_then(|(parent, _)| default_children(parent))
+                .ok_or(CLIError::Endpoint)?,
+        };
+
+        let response = match parent {
+            Some((parent, id)) => {
+                self.client
+                    .child_list(endpoint, id.as_str(), parent.clone(), None)?
+            }
+            None => self.client.list(endpoint, vec![])?,
+        };
+
+        Renderer::new(Format::Table, &DEFAULT_COLUMNS)
+            .render(Output::Json(serde_json::from_str(response.as_str())?))
+    }
+
+    fn cat(&self, (endpoint, id): (Endpoints, String)) -> Result<String, CLIError> {
+        let response = self.client.get(endpoint, id.as_str(), None)?;
+
+        Renderer::new(Format::Pretty, &[])
+            .render(Output::Json(serde_json::from_str(response.as_str())?))
+    }
+
+    fn edit(&self, (endpoint, id): (Endpoints, String)) -> Result<String, CLIError> {
+        Renderer::new(Format::Pretty, &[]).render(edit::edit(
+            &self.client,
+            endpoint,
+            id.as_str(),
+            ConflictPolicy::Refuse,
+        )?)
+    }
+
+    fn mv(
+        &self,
+        (endpoint, id): (Endpoints, String),
+        (parent, parent_id): (Endpoints, String),
+        editor: &mut Editor<ShellHelper>,
+    ) -> Result<String, CLIError> {
+        let answer = editor
+            .readline(
+                format!(
+                    "Move {}/{} to {}/{}? [y/N] ",
+                    endpoint, id, parent, parent_id
+                )
+                .as_str(),
+            )
+            .map_err(CLIError::Shell)?;
+
+        if !["y", "yes"].contains(&answer.trim().to_lowercase().as_str()) {
+            return Err(CLIError::Aborted);
+        }
+
+        let done = format!("Moved {}/{} to {}/{}", endpoint, id, parent, parent_id);
+        let result = self
+            .client
+            .change_parent(parent, parent_id.as_str(), endpoint, id.as_str());
+
+        Renderer::new(Format::Pretty, &[]).render(write::report(result, done)?)
+    }
+
+    // Object ids differ between environments, so switching returns to the top level
+    fn env(&mut self, environment: &str) -> Result<String, CLIError> {
+        let environment = Environment::from_str(environment)?;
+
+        self.client = self.options.apply(crate::environment_client(
+            self.config_path.as_str(),
+            environment,
+        )?)?;
+        self.target = environment.to_string();
+        self.location.clear();
+
+        Ok(format!("Switched to {}", environment))
+    }
+}
+
+// Splits `<type>/<id>` into its endpoint and id
+fn parse_object(object: &str) -> Result<(Endpoints, String), CLIError> {
+    let mut parts = object.splitn(2, '/');
+    let endpoint = parse_endpoint(parts.next())?;
+
+    match parts.next() {
+        Some(id) if !id.is_empty() => Ok((endpoint, id.to_string())),
+        _ => Err(CLIError::MissingId),
+    }
+}
+
+// The type of object listed by `ls` when no type is given
+fn default_children(parent: &Endpoints) -> Option<Endpoints> {
+    match *parent {
+        Endpoints::Franchise => Some(Endpoints::Show),
+        Endpoints::Show => Some(Endpoints::Season),
+        Endpoints::Season => Some(Endpoints::Episode),
+        Endpoints::Episode | Endpoints::Special => Some(Endpoints::Asset),
+        _ => None,
+    }
+}
+
+// Completes command names, endpoint names and environments
+struct ShellHelper;
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let start = line[..pos].rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let word = &line[start..pos];
+        let command = line[..start].split_whitespace().next();
+
+        let candidates: Vec<String> = match command {
+            None => COMMANDS.iter().map(|c| c.to_string()).collect(),
+            Some("ls") => Endpoints::all().iter().map(|e| e.to_string()).collect(),
+            Some("cd") | Some("cat") | Some("edit") | Some("mv") => {
+                Endpoints::all().iter().map(|e| format!("{}/", e)).collect()
+            }
+            Some("env") => vec![
+                Environment::Live.to_string(),
+                Environment::Staging.to_string(),
+            ],
+            Some(_) => vec![],
+        };
+
+        let pairs = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .map(|candidate| Pair {
+                display: candidate.clone(),
+                replacement: candidate,
+            })
+            .collect();
+
+        Ok((start, pairs))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
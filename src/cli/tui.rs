@@ -0,0 +1,415 @@
+use clap::{App, ArgMatches, SubCommand};
+use crossterm::cursor::Show;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use mm_client::{Client, Endpoints, MMCResult};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+use serde_json::Value;
+
+use std::io::{self, Stdout};
+use std::panic;
+use std::sync::Arc;
+
+use crate::error::CLIError;
+use crate::output::{cell, lookup, Output};
+
+const HELP: &str = "↑/↓ move  enter/→ open  ←/backspace back  / search  q quit";
+
+// Attributes shown at the top of the detail pane, when present
+const DETAIL_ATTRIBUTES: [&str; 7] = [
+    "title",
+    "slug",
+    "premiered_on",
+    "encored_on",
+    "ordinal",
+    "object_type",
+    "description_short",
+];
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("tui").about(
+        "Opens a terminal browser over franchises, shows, seasons, specials, episodes and their \
+         assets",
+    )
+}
+
+pub fn run(_matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let franchises = all_pages(|params| client.franchises(params))?;
+    let mut browser = Browser {
+        client,
+        levels: vec![Level::new(Endpoints::Franchise.to_string(), franchises)],
+        searching: false,
+        status: String::new(),
+    };
+
+    // A panic would otherwise leave the terminal in raw mode on the alternate screen, hiding its
+    // message. The previous hook is put back once browsing ends.
+    let previous = Arc::new(panic::take_hook());
+    let hook = previous.clone();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        hook(info);
+    }));
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+
+    let result = Terminal::new(CrosstermBackend::new(stdout))
+        .map_err(CLIError::from)
+        .and_then(|mut terminal| browser.run(&mut terminal));
+
+    // Always hand the terminal back, even when browsing failed
+    restore_terminal()?;
+    panic::set_hook(Box::new(move |info| previous(info)));
+
+    result.map(|_| Output::Message(String::new()))
+}
+
+fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, Show)
+}
+
+// Fetches every page of a list using one of the client's shorthands
+fn all_pages<F>(fetch: F) -> Result<Vec<Value>, CLIError>
+where
+    F: Fn(Vec<(&str, &str)>) -> MMCResult<String>,
+{
+    let mut items = vec![];
+    let mut page = 1;
+
+    loop {
+        let page_string = page.to_string();
+        let body = fetch(vec![("page", page_string.as_str())])?;
+        let parsed: Value = serde_json::from_str(body.as_str()).map_err(CLIError::Format)?;
+
+        if let Some(Value::Array(data)) = parsed.get("data") {
+            items.extend(data.iter().cloned());
+        }
+
+        if Client::next_page(&parsed).is_none() {
+            return Ok(items);
+        }
+
+        page += 1;
+    }
+}
+
+// A loaded list of objects along with the selection and search within it
+struct Level {
+    title: String,
+    items: Vec<Value>,
+    search: String,
+    state: ListState,
+}
+
+impl Level {
+    fn new(title: String, items: Vec<Value>) -> Level {
+        let mut state = ListState::default();
+        state.select(if items.is_empty() { None } else { Some(0) });
+
+        Level {
+            title,
+            items,
+            search: String::new(),
+            state,
+        }
+    }
+
+    // The items matching the current search, compared case insensitively against the title
+    fn visible(&self) -> Vec<&Value> {
+        let search = self.search.to_lowercase();
+
+        self.items
+            .iter()
+            .filter(|item| search.is_empty() || title(item).to_lowercase().contains(&search))
+            .collect()
+    }
+
+    fn selected(&self) -> Option<&Value> {
+        self.state
+            .selected()
+            .and_then(|index| self.visible().get(index).cloned())
+    }
+
+    fn select_by(&mut self, offset: isize) {
+        let count = self.visible().len();
+
+        if count == 0 {
+            self.state.select(None);
+            return;
+        }
+
+        let current = self.state.selected().unwrap_or(0) as isize;
+        let next = (current + offset).max(0).min(count as isize - 1);
+        self.state.select(Some(next as usize));
+    }
+}
+
+struct Browser<'c> {
+    client: &'c Client,
+    levels: Vec<Level>,
+    searching: bool,
+    status: String,
+}
+
+impl<'c> Browser<'c> {
+    fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<(), CLIError> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                if self.searching {
+                    self.search_key(key);
+                } else if !self.browse_key(key) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    // Handles a key while browsing, returning false when the browser should close
+    fn browse_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.current().select_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.current().select_by(1),
+            KeyCode::PageUp => self.current().select_by(-10),
+            KeyCode::PageDown => self.current().select_by(10),
+            KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') if self.levels.len() > 1 => {
+                self.levels.pop();
+            }
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char('l') => self.descend(),
+            KeyCode::Char('/') => {
+                self.searching = true;
+                self.current().search.clear();
+            }
+            _ => {}
+        }
+
+        true
+    }
+
+    fn search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.searching = false,
+            KeyCode::Esc => {
+                self.searching = false;
+                self.current().search.clear();
+            }
+            KeyCode::Backspace => {
+                self.current().search.pop();
+            }
+            KeyCode::Char(c) => self.current().search.push(c),
+            _ => {}
+        }
+
+        self.current().select_by(0);
+    }
+
+    fn current(&mut self) -> &mut Level {
+        let last = self.levels.len() - 1;
+        &mut self.levels[last]
+    }
+
+    // Opens the children of the selected object, when it has any in the hierarchy
+    fn descend(&mut self) {
+        let selected = self.current().selected().and_then(|item| {
+            let endpoint = item.get("type")?.as_str()?.parse::<Endpoints>().ok()?;
+            let id = item.get("id")?.as_str()?.to_string();
+
+            Some((endpoint, id))
+        });
+
+        if let Some((endpoint, id)) = selected {
+            match self.children(&endpoint, id.as_str()) {
+                Ok(Some((children, items))) => {
+                    let title = format!("{}/{}/{}", endpoint, id, children);
+                    self.levels.push(Level::new(title, items));
+                    self.status.clear();
+                }
+                Ok(None) => {}
+                Err(err) => self.status = err.to_string(),
+            }
+        }
+    }
+
+    // Fetches the next level down in the franchise, show, season, episode and asset hierarchy,
+    // along with a name for it. Shows list their specials after their seasons.
+    fn children(
+        &self,
+        endpoint: &Endpoints,
+        id: &str,
+    ) -> Result<Option<(String, Vec<Value>)>, CLIError> {
+        let client = self.client;
+
+        let children = match *endpoint {
+            // There is no shorthand for the shows of a franchise
+            Endpoints::Franchise => (
+                Endpoints::Show.to_string(),
+                all_pages(|params| {
+                    client.child_list(Endpoints::Show, id, Endpoints::Franchise, Some(params))
+                })?,
+            ),
+            Endpoints::Show => {
+                let mut items = all_pages(|params| client.seasons(id, Some(params)))?;
+                items.extend(all_pages(|params| client.specials(id, Some(params)))?);
+
+                (
+                    format!("{}+{}", Endpoints::Season, Endpoints::Special),
+                    items,
+                )
+            }
+            Endpoints::Season => (
+                Endpoints::Episode.to_string(),
+                all_pages(|params| client.episodes(id, Some(params)))?,
+            ),
+            Endpoints::Episode | Endpoints::Special => (
+                Endpoints::Asset.to_string(),
+                all_pages(|params| client.assets(id, endpoint.clone(), Some(params)))?,
+            ),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(children))
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(frame.size());
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(rows[0]);
+
+        let searching = self.searching;
+        let status = if searching {
+            format!("/{}", self.current().search)
+        } else if !self.status.is_empty() {
+            self.status.clone()
+        } else {
+            HELP.to_string()
+        };
+
+        let level = self.current();
+        let heading = match level.search.as_str() {
+            "" => level.title.clone(),
+            search => format!("{} (matching {})", level.title, search),
+        };
+        let items: Vec<ListItem> = level
+            .visible()
+            .iter()
+            .map(|item| ListItem::new(title(item)))
+            .collect();
+        let detail = detail_lines(level.selected());
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(heading))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, columns[0], &mut level.state);
+
+        draw_detail(frame, columns[1], detail);
+        frame.render_widget(Paragraph::new(status), rows[1]);
+    }
+}
+
+fn draw_detail(frame: &mut Frame, area: Rect, lines: Vec<Line<'static>>) {
+    let detail = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("detail"))
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(detail, area);
+}
+
+// Specials are marked as such, as they are listed alongside seasons
+fn title(item: &Value) -> String {
+    let title = lookup(item, "attributes.title")
+        .or_else(|| item.get("id"))
+        .map(|value| cell(Some(value)))
+        .unwrap_or_default();
+
+    match item.get("type").and_then(Value::as_str) {
+        Some("special") => format!("{} (special)", title),
+        _ => title,
+    }
+}
+
+// Lists the key attributes, availability windows and images of an object
+fn detail_lines(item: Option<&Value>) -> Vec<Line<'static>> {
+    let item = match item {
+        Some(item) => item,
+        None => return vec![Line::from("Nothing selected")],
+    };
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let field = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{}: ", name), bold),
+            Span::raw(value),
+        ])
+    };
+
+    let mut lines = vec![
+        field("id", cell(item.get("id"))),
+        field("type", cell(item.get("type"))),
+    ];
+
+    for name in DETAIL_ATTRIBUTES.iter() {
+        if let Some(value) = lookup(item, format!("attributes.{}", name).as_str()) {
+            if !value.is_null() {
+                lines.push(field(name, cell(Some(value))));
+            }
+        }
+    }
+
+    if let Some(Value::Object(windows)) = lookup(item, "attributes.availabilities") {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Availability", bold)));
+
+        for (name, window) in windows {
+            let start = cell(window.get("start"));
+            let end = cell(window.get("end"));
+            lines.push(field(
+                name,
+                format!(
+                    "{} to {}",
+                    if start.is_empty() {
+                        "-"
+                    } else {
+                        start.as_str()
+                    },
+                    if end.is_empty() { "-" } else { end.as_str() }
+                ),
+            ));
+        }
+    }
+
+    if let Some(Value::Array(images)) = lookup(item, "attributes.images") {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Images", bold)));
+
+        for image in images {
+            lines.push(field(
+                cell(image.get("profile")).as_str(),
+                cell(image.get("image")),
+            ));
+        }
+    }
+
+    lines
+}
@@ -0,0 +1,75 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use mm_client::{Client, FileJournal, Journal, JournalEntry, MMCError};
+use serde_json::Value;
+
+use crate::error::CLIError;
+use crate::output::Output;
+use crate::write::{confirm, report, yes_arg};
+
+// The columns shown for each journal entry when listed as a table or CSV
+pub const JOURNAL_COLUMNS: [&str; 5] = ["id", "timestamp", "operation", "endpoint", "object_id"];
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("undo")
+        .about("Reverts an update or delete recorded in the journal, or lists the journal entries")
+        .arg(
+            Arg::with_name("entry")
+                .conflicts_with("last")
+                .help("Id of the journal entry to undo. Lists the entries if omitted"),
+        )
+        .arg(
+            Arg::with_name("last")
+                .long("last")
+                .takes_value(false)
+                .help("Undoes the most recent entry in the journal"),
+        )
+        .arg(yes_arg())
+}
+
+pub fn run(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let journal = FileJournal::new(
+        matches
+            .value_of("journal")
+            .ok_or(CLIError::MissingJournal)?,
+    );
+    let mut entries = journal.entries()?;
+
+    let entry = match (matches.value_of("entry"), matches.is_present("last")) {
+        (Some(id), _) => journal.entry(id)?,
+        (None, true) => entries
+            .pop()
+            .ok_or_else(|| MMCError::Undo("The journal has no entries".to_string()))?,
+        (None, false) => return list(entries),
+    };
+
+    let target = format!("{}/{}", entry.endpoint, entry.object_id);
+    let operation = format!("{:?}", entry.operation).to_lowercase();
+    confirm(
+        matches,
+        format!("Undo the {} of {}?", operation, target).as_str(),
+    )?;
+
+    report(
+        client.undo(&entry),
+        format!("Undid the change to {}", target),
+    )
+}
+
+// Lists the entries without the captured objects, which are too large to be read in a list
+fn list(entries: Vec<JournalEntry>) -> Result<Output, CLIError> {
+    let data = entries
+        .iter()
+        .map(|entry| {
+            let mut value = serde_json::to_value(entry)?;
+
+            if let Value::Object(ref mut fields) = value {
+                fields.remove("previous");
+                fields.remove("changes");
+            }
+
+            Ok(value)
+        })
+        .collect::<Result<Vec<Value>, CLIError>>()?;
+
+    Ok(Output::Json(serde_json::json!({ "data": data })))
+}
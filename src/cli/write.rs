@@ -0,0 +1,191 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use mm_client::{Client, MMCResult};
+use serde_json::Value;
+
+use std::fs::File;
+use std::io::{self, BufRead, Read, Write};
+
+use crate::error::CLIError;
+use crate::list::parse_endpoint;
+use crate::output::Output;
+
+pub fn create_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("create")
+        .about("Creates a new object belonging to a parent object")
+        .arg(
+            Arg::with_name("parent-type")
+                .required(true)
+                .help("Object type of the parent"),
+        )
+        .arg(
+            Arg::with_name("parent-id")
+                .required(true)
+                .help("Object id of the parent"),
+        )
+        .arg(
+            Arg::with_name("type")
+                .required(true)
+                .help("Object type to create"),
+        )
+        .arg(file_arg())
+}
+
+pub fn update_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("update")
+        .about("Updates an existing object")
+        .arg(
+            Arg::with_name("type")
+                .required(true)
+                .help("Object type to update"),
+        )
+        .arg(
+            Arg::with_name("id")
+                .required(true)
+                .help("Object id to update"),
+        )
+        .arg(file_arg())
+}
+
+pub fn delete_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("delete")
+        .about("Deletes an existing object")
+        .arg(
+            Arg::with_name("type")
+                .required(true)
+                .help("Object type to delete"),
+        )
+        .arg(
+            Arg::with_name("id")
+                .required(true)
+                .help("Object id to delete"),
+        )
+        .arg(yes_arg())
+}
+
+pub fn move_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("move")
+        .about("Moves an object to a new parent object")
+        .arg(
+            Arg::with_name("type")
+                .required(true)
+                .help("Object type to move"),
+        )
+        .arg(
+            Arg::with_name("id")
+                .required(true)
+                .help("Object id to move"),
+        )
+        .arg(
+            Arg::with_name("parent-type")
+                .required(true)
+                .help("Object type of the new parent"),
+        )
+        .arg(
+            Arg::with_name("parent-id")
+                .required(true)
+                .help("Object id of the new parent"),
+        )
+        .arg(yes_arg())
+}
+
+fn file_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("file")
+        .short("f")
+        .long("file")
+        .takes_value(true)
+        .help("JSON file containing the request body. Reads from stdin if omitted or -")
+}
+
+pub fn yes_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("yes")
+        .short("y")
+        .long("yes")
+        .takes_value(false)
+        .help("Skips the confirmation prompt")
+}
+
+pub fn run_create(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let parent = parse_endpoint(matches.value_of("parent-type"))?;
+    let parent_id = matches.value_of("parent-id").unwrap_or_default();
+    let endpoint = parse_endpoint(matches.value_of("type"))?;
+    let body = read_body(matches.value_of("file"))?;
+
+    let done = format!("Created {} under {}/{}", endpoint, parent, parent_id);
+    report(client.create(parent, parent_id, endpoint, &body), done)
+}
+
+pub fn run_update(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let endpoint = parse_endpoint(matches.value_of("type"))?;
+    let id = matches.value_of("id").unwrap_or_default();
+    let body = read_body(matches.value_of("file"))?;
+
+    let done = format!("Updated {}/{}", endpoint, id);
+    report(client.update(endpoint, id, &body), done)
+}
+
+pub fn run_delete(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let endpoint = parse_endpoint(matches.value_of("type"))?;
+    let id = matches.value_of("id").unwrap_or_default();
+
+    confirm(matches, format!("Delete {}/{}?", endpoint, id).as_str())?;
+
+    let done = format!("Deleted {}/{}", endpoint, id);
+    report(client.delete(endpoint, id), done)
+}
+
+pub fn run_move(matches: &ArgMatches, client: &Client) -> Result<Output, CLIError> {
+    let endpoint = parse_endpoint(matches.value_of("type"))?;
+    let id = matches.value_of("id").unwrap_or_default();
+    let parent = parse_endpoint(matches.value_of("parent-type"))?;
+    let parent_id = matches.value_of("parent-id").unwrap_or_default();
+
+    confirm(
+        matches,
+        format!("Move {}/{} to {}/{}?", endpoint, id, parent, parent_id).as_str(),
+    )?;
+
+    let done = format!("Moved {}/{} to {}/{}", endpoint, id, parent, parent_id);
+    report(client.change_parent(parent, parent_id, endpoint, id), done)
+}
+
+// Reads and validates a JSON request body from a file, or from stdin when no file is given
+pub fn read_body(file: Option<&str>) -> Result<Value, CLIError> {
+    let mut contents = String::new();
+
+    match file {
+        Some(path) if path != "-" => File::open(path)?.read_to_string(&mut contents)?,
+        _ => io::stdin().read_to_string(&mut contents)?,
+    };
+
+    serde_json::from_str(contents.as_str()).map_err(CLIError::InvalidBody)
+}
+
+// Asks the user to confirm a destructive action unless --yes was supplied
+pub fn confirm(matches: &ArgMatches, prompt: &str) -> Result<(), CLIError> {
+    // Nothing is changed by a dry run, so there is nothing to confirm
+    if matches.is_present("yes") || matches.is_present("dry-run") {
+        return Ok(());
+    }
+
+    print!("{} [y/N] ", prompt);
+    let _ = io::stdout().flush();
+
+    // A closed or unreadable stdin, as in scripts, is treated as declining
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return Err(CLIError::Aborted);
+    }
+
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Ok(()),
+        _ => Err(CLIError::Aborted),
+    }
+}
+
+// Mutating endpoints may respond without a body, in which case a summary is reported instead
+pub fn report(result: MMCResult<String>, done: String) -> Result<Output, CLIError> {
+    match result {
+        Ok(ref body) if body.trim().is_empty() => Ok(Output::Message(done)),
+        result => crate::handle_client_response(result),
+    }
+}
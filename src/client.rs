@@ -1,38 +1,85 @@
 extern crate reqwest;
 extern crate serde;
 
-#[cfg(test)]
-use mockito;
-
-use reqwest::blocking::{Client as NetworkClient, RequestBuilder, Response};
-use reqwest::header::CONNECTION;
-use reqwest::{Method, StatusCode};
-use serde::Serialize;
+use chrono::Utc;
+use reqwest::blocking::{Client as NetworkClient, Response};
+use reqwest::header::{CONNECTION, CONTENT_TYPE};
+use reqwest::{Method, StatusCode, Url};
+use serde::de::{self, Deserialize, Deserializer};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
 
 use std::fmt;
 use std::io::Read;
 use std::str;
-
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::audit::{AuditRecord, AuditSink};
+use crate::batch::Operation;
+use crate::credentials::{CredentialProvider, Credentials, Environment};
+use crate::diff::{attributes_of, AttributeDiff, ChangedUpdate, ConflictPolicy};
+use crate::draft::{AssetDraft, Draft};
+use crate::dry_run::PlannedRequest;
 use crate::error::MMCError;
 use crate::error::MMCResult;
+use crate::interceptor::{ApiRequest, ApiResponse, Interceptor};
+use crate::journal::{Journal, JournalEntry};
+use crate::metrics::{MetricsSink, RequestMetric};
 
 #[cfg(not(test))]
-const LIVE_URL: &'static str = "https://media.services.pbs.org/api/v1";
+const LIVE_URL: &str = "https://media.services.pbs.org/api/v1";
 #[cfg(not(test))]
-const STAGING_URL: &'static str = "https://media-staging.services.pbs.org/api/v1";
+const STAGING_URL: &str = "https://media-staging.services.pbs.org/api/v1";
 
 #[cfg(test)]
-const LIVE_URL: &'static str = mockito::SERVER_URL;
+#[allow(deprecated)]
+const LIVE_URL: &str = mockito::SERVER_URL;
 #[cfg(test)]
-const STAGING_URL: &'static str = mockito::SERVER_URL;
+#[allow(deprecated)]
+const STAGING_URL: &str = mockito::SERVER_URL;
+
+// The attributes of an edit object that are assigned by Media Manager, and so are not sent when
+// a deleted object is recreated by undo
+const READ_ONLY_ATTRIBUTES: [&str; 8] = [
+    "id",
+    "slug",
+    "cid",
+    "links",
+    "created_at",
+    "updated_at",
+    "legacy_tp_media_id",
+    "tp_media_id",
+];
 
 /// A client for communicating with the Media Manager API
-#[derive(Debug)]
 pub struct Client {
-    key: String,
-    secret: String,
+    credentials: Credentials,
     base: String,
     client: NetworkClient,
+    interceptors: Vec<Box<dyn Interceptor>>,
+    metrics: Option<Arc<dyn MetricsSink>>,
+    audit: Option<(Arc<dyn AuditSink>, String)>,
+    journal: Option<Arc<dyn Journal>>,
+    allow_foreign_hosts: bool,
+    dry_run: bool,
+    retries: usize,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("credentials", &self.credentials)
+            .field("base", &self.base)
+            .field("interceptors", &self.interceptors)
+            .field("metrics", &self.metrics)
+            .field("audit", &self.audit)
+            .field("journal", &self.journal)
+            .field("allow_foreign_hosts", &self.allow_foreign_hosts)
+            .field("dry_run", &self.dry_run)
+            .field("retries", &self.retries)
+            .finish()
+    }
 }
 
 pub type Params<'a> = Vec<(&'a str, &'a str)>;
@@ -77,7 +124,7 @@ struct MoveRequest {
 }
 
 /// The Media Manager endpoints that are supported by [Client](struct.Client.html)
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Endpoints {
     /// Represents the assets endpoint
     Asset,
@@ -105,7 +152,22 @@ pub enum Endpoints {
 }
 
 impl Endpoints {
-    fn singular(&self) -> String {
+    /// Returns every endpoint of the Media Manager API
+    pub fn all() -> Vec<Endpoints> {
+        vec![
+            Endpoints::Asset,
+            Endpoints::Changelog,
+            Endpoints::Collection,
+            Endpoints::Episode,
+            Endpoints::Franchise,
+            Endpoints::Season,
+            Endpoints::Show,
+            Endpoints::Special,
+        ]
+    }
+
+    /// Returns the singular form of the endpoint, as used for the `type` of its objects
+    pub fn singular(&self) -> String {
         match *self {
             Endpoints::Asset => "asset",
             Endpoints::Changelog => "changelog",
@@ -137,6 +199,20 @@ impl fmt::Display for Endpoints {
     }
 }
 
+impl Serialize for Endpoints {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Endpoints {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
 impl str::FromStr for Endpoints {
     type Err = MMCError;
 
@@ -158,38 +234,123 @@ impl str::FromStr for Endpoints {
 impl Client {
     /// Generates a new client for the production Media Manager API
     pub fn new(key: &str, secret: &str) -> MMCResult<Client> {
-        Client::client_builder(key, secret, LIVE_URL)
+        Client::client_builder(Credentials::new(key, secret), LIVE_URL)
     }
 
     /// Generates a new client for the staging Media Manager API
     pub fn staging(key: &str, secret: &str) -> MMCResult<Client> {
-        Client::client_builder(key, secret, STAGING_URL)
+        Client::client_builder(Credentials::new(key, secret), STAGING_URL)
+    }
+
+    /// Generates a new client for a Media Manager API hosted at a custom base url
+    pub fn custom(key: &str, secret: &str, base: &str) -> MMCResult<Client> {
+        Client::client_builder(Credentials::new(key, secret), base.trim_end_matches('/'))
+    }
+
+    /// Generates a new client for the requested [Environment](enum.Environment.html) using
+    /// credentials looked up from the supplied [CredentialProvider](trait.CredentialProvider.html)
+    pub fn from_provider<P: CredentialProvider + ?Sized>(
+        provider: &P,
+        environment: Environment,
+    ) -> MMCResult<Client> {
+        let base = match environment {
+            Environment::Live => LIVE_URL,
+            Environment::Staging => STAGING_URL,
+        };
+
+        Client::client_builder(provider.credentials(environment)?, base)
     }
 
-    fn client_builder(key: &str, secret: &str, base: &str) -> MMCResult<Client> {
+    fn client_builder(credentials: Credentials, base: &str) -> MMCResult<Client> {
         NetworkClient::builder()
             .build()
             .map_err(MMCError::Network)
-            .and_then(|net_client| {
-                Ok(Client {
-                    key: String::from(key),
-                    secret: String::from(secret),
-                    base: String::from(base),
-                    client: net_client,
-                })
+            .map(|net_client| Client {
+                credentials,
+                base: String::from(base),
+                client: net_client,
+                interceptors: vec![],
+                metrics: None,
+                audit: None,
+                journal: None,
+                allow_foreign_hosts: false,
+                dry_run: false,
+                retries: 0,
             })
     }
 
+    /// Adds an [Interceptor](trait.Interceptor.html) to the end of the client's interceptor chain
+    pub fn with_interceptor<I: Interceptor + 'static>(mut self, interceptor: I) -> Client {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+
+    /// Sets the [MetricsSink](trait.MetricsSink.html) that the client reports every request to
+    pub fn with_metrics<M: MetricsSink + 'static>(mut self, sink: Arc<M>) -> Client {
+        self.metrics = Some(sink);
+        self
+    }
+
+    /// Sets the [AuditSink](trait.AuditSink.html) that every create, update, delete and move sent
+    /// by the client is recorded to, along with the label of the operator making the changes
+    pub fn with_audit<A: AuditSink + 'static>(mut self, sink: Arc<A>, operator: &str) -> Client {
+        self.audit = Some((sink, operator.to_string()));
+        self
+    }
+
+    /// Sets the [Journal](trait.Journal.html) that the state of an object is captured to, using
+    /// `edit`, before it is updated or deleted. The captured change can later be reverted with
+    /// `undo`. An update or delete is not sent if its object's state can not be captured, and the
+    /// captured state is only added to the journal once the API has accepted the change.
+    pub fn with_journal<J: Journal + 'static>(mut self, journal: Arc<J>) -> Client {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Allows the client to send credentials to hosts other than the configured API host. By
+    /// default requests to any other host are refused.
+    pub fn allow_foreign_hosts(mut self, allow: bool) -> Client {
+        self.allow_foreign_hosts = allow;
+        self
+    }
+
+    /// Puts the client into dry-run mode. A dry-run client builds and validates the requests made
+    /// by `create`, `update`, `delete` and `change_parent` without sending them, returning the
+    /// [PlannedRequest](struct.PlannedRequest.html) as JSON instead. Requests that only read from
+    /// the API are still sent.
+    pub fn dry_run(mut self, dry_run: bool) -> Client {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Sets the number of times a request that only reads from the API is retried when it can not
+    /// be sent or the server responds with a 5xx status. Requests that change data are never
+    /// retried. Each retry is reported to the client's [MetricsSink](trait.MetricsSink.html).
+    pub fn with_retries(mut self, retries: usize) -> Client {
+        self.retries = retries;
+        self
+    }
+
+    /// Returns true when the client is in dry-run mode
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
     /// Attempts to fetch a single object with the requested id from the requested
     /// Media Manager API endpoint
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(endpoint = %endpoint, id = id))
+    )]
     pub fn get(&self, endpoint: Endpoints, id: &str, params: Option<Params>) -> MMCResult<String> {
         self.rq_get(
+            Some(endpoint.clone()),
             Client::build_url(
                 self.base.as_str(),
                 None,
                 endpoint,
                 Some(id),
-                params.unwrap_or(vec![]),
+                params.unwrap_or_default(),
             )
             .as_str(),
         )
@@ -197,12 +358,23 @@ impl Client {
 
     /// Attempts to fetch a list of objects from the requested Media Manager API endpoint augmented
     /// by the requested parameters
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(endpoint = %endpoint)))]
     pub fn list(&self, endpoint: Endpoints, params: Params) -> MMCResult<String> {
-        self.rq_get(Client::build_url(self.base.as_str(), None, endpoint, None, params).as_str())
+        self.rq_get(
+            Some(endpoint.clone()),
+            Client::build_url(self.base.as_str(), None, endpoint, None, params).as_str(),
+        )
     }
 
     /// Attempts to fetch a list of child objects of the requested Media Manager API type belonging
     /// to the requested parent object augmeted by the requested parameters
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(endpoint = %endpoint, parent = %parent_endpoint, parent_id = parent_id)
+        )
+    )]
     pub fn child_list(
         &self,
         endpoint: Endpoints,
@@ -211,12 +383,13 @@ impl Client {
         params: Option<Params>,
     ) -> MMCResult<String> {
         self.rq_get(
+            Some(endpoint.clone()),
             Client::build_url(
                 self.base.as_str(),
                 Some((parent_endpoint, parent_id)),
                 endpoint,
                 None,
-                params.unwrap_or(Vec::new()),
+                params.unwrap_or_default(),
             )
             .as_str(),
         )
@@ -224,6 +397,13 @@ impl Client {
 
     /// Attempts to create a new object of the provided [Endpoints](enum.Endpoints.html) for the
     /// provided parent [Endpoints](enum.Endpoints.html)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(endpoint = %endpoint, parent = %parent, parent_id = id)
+        )
+    )]
     pub fn create<T: Serialize>(
         &self,
         parent: Endpoints,
@@ -232,6 +412,7 @@ impl Client {
         body: &T,
     ) -> MMCResult<String> {
         self.rq_post(
+            Some(endpoint.clone()),
             Client::build_url(
                 self.base.as_str(),
                 Some((parent, id)),
@@ -244,34 +425,172 @@ impl Client {
         )
     }
 
+    /// Validates a [Draft](trait.Draft.html) and creates the object it describes beneath the
+    /// parent with the supplied id
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(endpoint = %draft.endpoint(), parent_id = parent_id))
+    )]
+    pub fn create_draft<D: Draft>(&self, parent_id: &str, draft: &D) -> MMCResult<String> {
+        if let Some(id) = draft.id() {
+            return Err(MMCError::InvalidDraft(format!(
+                "{} draft edits the existing object {} and can not be created",
+                draft.endpoint().singular(),
+                id
+            )));
+        }
+
+        draft.validate()?;
+        self.create(draft.parent(), parent_id, draft.endpoint(), draft)
+    }
+
+    /// Validates an [AssetDraft](struct.AssetDraft.html) and creates the asset it describes
+    /// beneath the parent specified by the [Endpoints](enum.Endpoints.html) and id
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(parent = %parent, parent_id = parent_id))
+    )]
+    pub fn create_asset(
+        &self,
+        parent: Endpoints,
+        parent_id: &str,
+        draft: &AssetDraft,
+    ) -> MMCResult<String> {
+        match parent {
+            Endpoints::Episode
+            | Endpoints::Special
+            | Endpoints::Season
+            | Endpoints::Show
+            | Endpoints::Franchise => (),
+            other => {
+                return Err(MMCError::InvalidDraft(format!(
+                    "asset can not be created beneath a {}",
+                    other.singular()
+                )))
+            }
+        }
+
+        draft.validate()?;
+        self.create(parent, parent_id, Endpoints::Asset, draft)
+    }
+
     /// Attempts to fetch the edit object specified by the [Endpoints](enum.Endpoints.html) and id
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(endpoint = %endpoint, id = id))
+    )]
     pub fn edit(&self, endpoint: Endpoints, id: &str) -> MMCResult<String> {
         self.rq_get(
+            Some(endpoint.clone()),
             Client::build_edit_url(self.base.as_str(), None, endpoint, Some(id), vec![]).as_str(),
         )
     }
 
     /// Attempts to update the object specified by the [Endpoints](enum.Endpoints.html) and id
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(endpoint = %endpoint, id = id))
+    )]
     pub fn update<T: Serialize>(
         &self,
         endpoint: Endpoints,
         id: &str,
         body: &T,
     ) -> MMCResult<String> {
-        self.rq_patch(
+        let changes = serde_json::to_value(body).map_err(MMCError::Serialize)?;
+        let entry = self.capture(Operation::Update, &endpoint, id, Some(changes))?;
+
+        let response = self.rq_patch(
+            Some(endpoint.clone()),
             Client::build_edit_url(self.base.as_str(), None, endpoint, Some(id), vec![]).as_str(),
             body,
-        )
+        )?;
+
+        self.journal_entry(entry)?;
+        Ok(response)
+    }
+
+    /// Validates a [Draft](trait.Draft.html) made with `edit` and updates the object it describes
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(endpoint = %draft.endpoint()))
+    )]
+    pub fn update_draft<D: Draft>(&self, draft: &D) -> MMCResult<String> {
+        let id = draft.id().ok_or_else(|| {
+            MMCError::InvalidDraft(format!(
+                "{} draft creates a new object and can not be used for an update",
+                draft.endpoint().singular()
+            ))
+        })?;
+
+        draft.validate()?;
+        self.update(draft.endpoint(), id, draft)
+    }
+
+    /// Fetches the edit object specified by the [Endpoints](enum.Endpoints.html) and id and
+    /// updates only the attributes of `desired` that differ from it. No update is sent when
+    /// nothing differs.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(endpoint = %endpoint, id = id))
+    )]
+    pub fn update_changed(
+        &self,
+        endpoint: Endpoints,
+        id: &str,
+        desired: &Value,
+    ) -> MMCResult<ChangedUpdate> {
+        self.changed(endpoint, id, desired, None, ConflictPolicy::Warn)
+    }
+
+    /// Works as `update_changed`, additionally checking whether any of the attributes to be sent
+    /// have been changed on the server since `baseline` was fetched. Conflicting attributes are
+    /// handled according to the [ConflictPolicy](enum.ConflictPolicy.html).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(endpoint = %endpoint, id = id))
+    )]
+    pub fn update_changed_since(
+        &self,
+        endpoint: Endpoints,
+        id: &str,
+        desired: &Value,
+        baseline: &Value,
+        policy: ConflictPolicy,
+    ) -> MMCResult<ChangedUpdate> {
+        self.changed(endpoint, id, desired, Some(baseline), policy)
     }
 
     /// Attempts to delete the object specified by the [Endpoints](enum.Endpoints.html) and id
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(endpoint = %endpoint, id = id))
+    )]
     pub fn delete(&self, endpoint: Endpoints, id: &str) -> MMCResult<String> {
-        self.rq_delete(
+        let entry = self.capture(Operation::Delete, &endpoint, id, None)?;
+
+        let response = self.rq_delete(
+            Some(endpoint.clone()),
             Client::build_edit_url(self.base.as_str(), None, endpoint, Some(id), vec![]).as_str(),
-        )
+        )?;
+
+        self.journal_entry(entry)?;
+        Ok(response)
     }
 
     /// Attempts to change the parent of an object
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                endpoint = %child_endpoint,
+                id = child_id,
+                parent = %parent_endpoint,
+                parent_id = parent_id
+            )
+        )
+    )]
     pub fn change_parent(
         &self,
         parent_endpoint: Endpoints,
@@ -288,6 +607,7 @@ impl Client {
         };
 
         self.rq_patch(
+            Some(child_endpoint.clone()),
             Client::build_url(
                 self.base.as_str(),
                 None,
@@ -300,17 +620,109 @@ impl Client {
         )
     }
 
-    /// Allows for calling any arbitrary url from the Media Manager API
+    /// Reverts the change recorded by a [JournalEntry](struct.JournalEntry.html). Updates are
+    /// undone by restoring the previous values of the attributes that were sent, and deletes are
+    /// undone by recreating the object beneath its parent from its previous attributes, leaving out
+    /// those assigned by Media Manager such as its id, slug and timestamps. A recreated object is
+    /// given a new id.
+    ///
+    /// Undoing an update is captured by the journal like any other update, and so can itself be
+    /// undone. Recreating a deleted object is not captured, so undoing a delete can not be undone.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(endpoint = %entry.endpoint, id = %entry.object_id, entry = %entry.id)
+        )
+    )]
+    pub fn undo(&self, entry: &JournalEntry) -> MMCResult<String> {
+        let empty = serde_json::Map::new();
+        let previous = attributes_of(&entry.previous)?;
+        let object_type = entry
+            .previous
+            .pointer("/data/type")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .unwrap_or_else(|| entry.endpoint.singular());
+
+        match entry.operation {
+            Operation::Update => {
+                let sent = match entry.changes {
+                    Some(ref changes) => attributes_of(changes)?,
+                    None => &empty,
+                };
+                let restore = AttributeDiff {
+                    changed: sent
+                        .keys()
+                        .map(|name| {
+                            (
+                                name.clone(),
+                                previous.get(name).cloned().unwrap_or(Value::Null),
+                            )
+                        })
+                        .collect(),
+                };
+
+                self.update(
+                    entry.endpoint.clone(),
+                    entry.object_id.as_str(),
+                    &restore.patch_body(object_type.as_str(), entry.object_id.as_str()),
+                )
+            }
+            Operation::Delete => {
+                let (parent, parent_id) = match (entry.parent.as_ref(), entry.parent_id.as_ref()) {
+                    (Some(parent), Some(parent_id)) => (parent.clone(), parent_id),
+                    _ => {
+                        return Err(MMCError::Undo(format!(
+                            "The parent of {}/{} is not known",
+                            entry.endpoint, entry.object_id
+                        )))
+                    }
+                };
+
+                // The parent is given by the url rather than the attributes, and attributes
+                // assigned by Media Manager are refused when creating an object
+                let mut attributes = previous.clone();
+                attributes.remove(parent.singular().as_str());
+
+                for name in READ_ONLY_ATTRIBUTES.iter() {
+                    attributes.remove(*name);
+                }
+
+                let body = serde_json::json!({
+                    "data": {
+                        "type": object_type,
+                        "attributes": attributes,
+                    }
+                });
+
+                self.create(parent, parent_id, entry.endpoint.clone(), &body)
+            }
+            operation => Err(MMCError::Undo(format!(
+                "{:?} operations are not journaled",
+                operation
+            ))),
+        }
+    }
+
+    /// Allows for calling any arbitrary url from the Media Manager API. Urls on hosts other than
+    /// the configured API host are refused unless `allow_foreign_hosts` has been enabled.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(url = url)))]
     pub fn url(&self, url: &str) -> MMCResult<String> {
-        self.rq_get(url)
+        self.rq_get(None, url)
     }
 
     /// Shorthand for accessing a single asset
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = id)))]
     pub fn asset(&self, id: &str, params: Option<Params>) -> MMCResult<String> {
         self.get(Endpoints::Asset, id, params)
     }
 
     /// Shorthand for accessing a list of assets
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(parent = %parent_endpoint, parent_id = parent_id))
+    )]
     pub fn assets(
         &self,
         parent_id: &str,
@@ -321,97 +733,400 @@ impl Client {
     }
 
     /// Shorthand for accessing a list of changes
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn changelog(&self, params: Params) -> MMCResult<String> {
         self.list(Endpoints::Changelog, params)
     }
 
     /// Shorthand for accessing a single collection
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = id)))]
     pub fn collection(&self, id: &str, params: Option<Params>) -> MMCResult<String> {
         self.get(Endpoints::Collection, id, params)
     }
 
     /// Shorthand for accessing a list of collections
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn collections(&self, params: Params) -> MMCResult<String> {
         self.list(Endpoints::Collection, params)
     }
 
     /// Shorthand for accessing a single episode
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = id)))]
     pub fn episode(&self, id: &str, params: Option<Params>) -> MMCResult<String> {
         self.get(Endpoints::Episode, id, params)
     }
 
     /// Shorthand for accessing a list of episodes
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(parent_id = season_id)))]
     pub fn episodes(&self, season_id: &str, params: Option<Params>) -> MMCResult<String> {
         self.child_list(Endpoints::Episode, season_id, Endpoints::Season, params)
     }
 
     /// Shorthand for accessing a single franchise
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = id)))]
     pub fn franchise(&self, id: &str, params: Option<Params>) -> MMCResult<String> {
         self.get(Endpoints::Franchise, id, params)
     }
 
     /// Shorthand for accessing a list of franchises
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn franchises(&self, params: Params) -> MMCResult<String> {
         self.list(Endpoints::Franchise, params)
     }
 
     /// Shorthand for accessing a single season
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = id)))]
     pub fn season(&self, id: &str, params: Option<Params>) -> MMCResult<String> {
         self.get(Endpoints::Season, id, params)
     }
 
     /// Shorthand for accessing a list of seasons
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(parent_id = show_id)))]
     pub fn seasons(&self, show_id: &str, params: Option<Params>) -> MMCResult<String> {
         self.child_list(Endpoints::Season, show_id, Endpoints::Show, params)
     }
 
     /// Shorthand for accessing a single special
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = id)))]
     pub fn special(&self, id: &str, params: Option<Params>) -> MMCResult<String> {
         self.get(Endpoints::Special, id, params)
     }
 
     /// Shorthand for accessing a list of specials
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(parent_id = show_id)))]
     pub fn specials(&self, show_id: &str, params: Option<Params>) -> MMCResult<String> {
         self.child_list(Endpoints::Special, show_id, Endpoints::Show, params)
     }
 
     /// Shorthand for accessing a single show
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = id)))]
     pub fn show(&self, id: &str, params: Option<Params>) -> MMCResult<String> {
         self.get(Endpoints::Show, id, params)
     }
 
     /// Shorthand for accessing a list of shows
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn shows(&self, params: Params) -> MMCResult<String> {
         self.list(Endpoints::Show, params)
     }
 
     // Handle read endpoints of the API
-    fn rq_get(&self, url: &str) -> MMCResult<String> {
-        self.rq_send(self.client.get(url))
+    fn rq_get(&self, endpoint: Option<Endpoints>, url: &str) -> MMCResult<String> {
+        self.rq_send(ApiRequest::new(Method::GET, url, None).for_endpoint(endpoint))
     }
 
     // Handle create endpoints of the API
-    fn rq_post<T: Serialize>(&self, url: &str, body: &T) -> MMCResult<String> {
-        self.rq_send(self.client.post(url).json(body))
+    fn rq_post<T: Serialize>(
+        &self,
+        endpoint: Option<Endpoints>,
+        url: &str,
+        body: &T,
+    ) -> MMCResult<String> {
+        self.rq_mutate(
+            ApiRequest::new(Method::POST, url, Some(Client::serialize_body(body)?))
+                .for_endpoint(endpoint),
+        )
     }
 
     // Handle update endpoints of the API
-    fn rq_patch<T: Serialize>(&self, url: &str, body: &T) -> MMCResult<String> {
-        self.rq_send(self.client.request(Method::PATCH, url).json(body))
+    fn rq_patch<T: Serialize>(
+        &self,
+        endpoint: Option<Endpoints>,
+        url: &str,
+        body: &T,
+    ) -> MMCResult<String> {
+        self.rq_mutate(
+            ApiRequest::new(Method::PATCH, url, Some(Client::serialize_body(body)?))
+                .for_endpoint(endpoint),
+        )
     }
 
     // Handle update endpoints of the API
-    fn rq_delete(&self, url: &str) -> MMCResult<String> {
-        self.rq_send(self.client.request(Method::DELETE, url))
+    fn rq_delete(&self, endpoint: Option<Endpoints>, url: &str) -> MMCResult<String> {
+        self.rq_mutate(ApiRequest::new(Method::DELETE, url, None).for_endpoint(endpoint))
+    }
+
+    fn changed(
+        &self,
+        endpoint: Endpoints,
+        id: &str,
+        desired: &Value,
+        baseline: Option<&Value>,
+        policy: ConflictPolicy,
+    ) -> MMCResult<ChangedUpdate> {
+        let current: Value = serde_json::from_str(self.edit(endpoint.clone(), id)?.as_str())
+            .map_err(MMCError::Parse)?;
+
+        let diff = AttributeDiff::between(&current, desired)?;
+        let conflicts = match baseline {
+            Some(baseline) => diff.conflicts(baseline, &current)?,
+            None => vec![],
+        };
+
+        if !conflicts.is_empty() && policy == ConflictPolicy::Refuse {
+            return Err(MMCError::Conflict(conflicts));
+        }
+
+        if diff.is_empty() {
+            return Ok(ChangedUpdate {
+                diff,
+                conflicts,
+                response: None,
+            });
+        }
+
+        let object_type = current
+            .pointer("/data/type")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .unwrap_or_else(|| endpoint.singular());
+
+        let response = self.update(endpoint, id, &diff.patch_body(object_type.as_str(), id))?;
+
+        Ok(ChangedUpdate {
+            diff,
+            conflicts,
+            response: Some(response),
+        })
+    }
+
+    // Capture the current state of an object before it is changed, when a journal is set
+    fn capture(
+        &self,
+        operation: Operation,
+        endpoint: &Endpoints,
+        id: &str,
+        changes: Option<Value>,
+    ) -> MMCResult<Option<JournalEntry>> {
+        if self.journal.is_none() || self.dry_run {
+            return Ok(None);
+        }
+
+        let previous = serde_json::from_str(self.edit(endpoint.clone(), id)?.as_str())
+            .map_err(MMCError::Parse)?;
+
+        Ok(Some(JournalEntry::capture(
+            operation,
+            endpoint.clone(),
+            id,
+            previous,
+            changes,
+        )))
+    }
+
+    // Add a captured entry to the journal once the change it records has been made, so that
+    // changes the API refused can not be undone
+    fn journal_entry(&self, entry: Option<JournalEntry>) -> MMCResult<()> {
+        match (self.journal.as_ref(), entry) {
+            (Some(journal), Some(entry)) => journal.append(&entry),
+            _ => Ok(()),
+        }
+    }
+
+    // Send a request that changes data, or describe it instead when in dry-run mode
+    fn rq_mutate(&self, request: ApiRequest) -> MMCResult<String> {
+        if !self.dry_run {
+            return self.rq_send(request);
+        }
+
+        self.check_host(request.url.as_str())?;
+
+        PlannedRequest::from_request(&request)
+            .and_then(|plan| serde_json::to_string(&plan).map_err(MMCError::Serialize))
+    }
+
+    // Run the interceptor chain around the request and map the response
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                method = %request.method,
+                url = %request.url,
+                status = tracing::field::Empty,
+                bytes = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    fn rq_send(&self, mut request: ApiRequest) -> MMCResult<String> {
+        let started = Instant::now();
+
+        let mut ran = 0;
+        let mut synthetic = None;
+
+        // Give each interceptor a chance to modify or short-circuit the request
+        for interceptor in self.interceptors.iter() {
+            ran += 1;
+            synthetic = interceptor.before_send(&mut request);
+
+            if synthetic.is_some() {
+                break;
+            }
+        }
+
+        let mut response = match synthetic {
+            Some(response) => response,
+            None => match self.rq_transmit_retrying(&request) {
+                Ok(response) => response,
+                Err(err) => {
+                    self.record_metrics(&request, started, Some(&err));
+                    self.record_audit(&request, None);
+                    return Err(err);
+                }
+            },
+        };
+
+        // Unwind the interceptors that saw the request in reverse order
+        for interceptor in self.interceptors[..ran].iter().rev() {
+            interceptor.after_response(&request, &mut response);
+        }
+
+        #[cfg(feature = "tracing")]
+        Client::trace_response(&response, started);
+
+        self.record_audit(&request, Some(&response));
+
+        let result = Client::handle_response(response);
+        self.record_metrics(&request, started, result.as_ref().err());
+
+        result
+    }
+
+    // Report the outcome of a request to the metrics sink
+    fn record_metrics(&self, request: &ApiRequest, started: Instant, error: Option<&MMCError>) {
+        if let Some(ref sink) = self.metrics {
+            sink.record_request(&RequestMetric {
+                endpoint: request.endpoint.as_ref(),
+                method: &request.method,
+                latency: started.elapsed(),
+                error,
+            });
+        }
+    }
+
+    // Report a request that changes data to the audit sink
+    fn record_audit(&self, request: &ApiRequest, response: Option<&ApiResponse>) {
+        let (sink, operator) = match self.audit {
+            Some((ref sink, ref operator)) if request.method != Method::GET => (sink, operator),
+            _ => return,
+        };
+
+        let body = request
+            .body
+            .as_ref()
+            .and_then(|body| serde_json::from_str::<Value>(body).ok());
+
+        // Creates return the new object's id, while updates and deletes only carry it in the url
+        let id = response
+            .and_then(|response| serde_json::from_str::<Value>(response.body.as_str()).ok())
+            .iter()
+            .chain(body.iter())
+            .find_map(|value| value.pointer("/data/id").and_then(Value::as_str))
+            .map(String::from)
+            .or_else(|| Client::id_from_url(request));
+
+        sink.record(&AuditRecord {
+            timestamp: Utc::now(),
+            operator: operator.clone(),
+            method: request.method.to_string(),
+            url: request.url.clone(),
+            body,
+            status: response.map(|response| response.status.as_u16()),
+            id,
+        });
+    }
+
+    // Finds the id that follows the requested endpoint in a url built by build_url
+    fn id_from_url(request: &ApiRequest) -> Option<String> {
+        let endpoint = request.endpoint.as_ref()?.to_string();
+        let url = Url::parse(request.url.as_str()).ok()?;
+        let segments: Vec<&str> = url.path_segments()?.collect();
+
+        segments
+            .iter()
+            .rposition(|segment| *segment == endpoint)
+            .and_then(|index| segments.get(index + 1))
+            .filter(|id| !id.is_empty() && **id != "edit")
+            .map(|id| id.to_string())
+    }
+
+    // Record the outcome of a request on the current span
+    #[cfg(feature = "tracing")]
+    fn trace_response(response: &ApiResponse, started: Instant) {
+        let span = tracing::Span::current();
+        span.record("status", response.status.as_u16());
+        span.record("bytes", response.body.len());
+        span.record("latency_ms", started.elapsed().as_millis() as u64);
+    }
+
+    // Transmit the request, sending reads again while they fail in a way that may be temporary
+    fn rq_transmit_retrying(&self, request: &ApiRequest) -> MMCResult<ApiResponse> {
+        let mut attempts = 0;
+
+        loop {
+            let result = self.rq_transmit(request);
+            let temporary = match result {
+                Err(MMCError::Network(_)) => true,
+                Ok(ref response) => response.status.is_server_error(),
+                Err(_) => false,
+            };
+
+            if !temporary || request.method != Method::GET || attempts >= self.retries {
+                return result;
+            }
+
+            attempts += 1;
+
+            if let Some(ref sink) = self.metrics {
+                sink.record_retry(request.endpoint.as_ref(), &request.method);
+            }
+        }
     }
 
-    // Handle authentication and response mapping
-    fn rq_send(&self, req: RequestBuilder) -> MMCResult<String> {
-        req.basic_auth(self.key.to_string(), Some(self.secret.to_string()))
+    // Handle authentication and sending of the request over the network
+    fn rq_transmit(&self, request: &ApiRequest) -> MMCResult<ApiResponse> {
+        self.check_host(request.url.as_str())?;
+
+        let mut req = self
+            .client
+            .request(request.method.clone(), request.url.as_str());
+
+        for (name, value) in request.headers.iter() {
+            req = req.header(name.as_str(), value.as_str());
+        }
+
+        if let Some(ref body) = request.body {
+            req = req
+                .header(CONTENT_TYPE, "application/json")
+                .body(body.clone());
+        }
+
+        req.basic_auth(self.credentials.key(), Some(self.credentials.secret()))
             .header(CONNECTION, "close")
             .send()
             .map_err(MMCError::Network)
-            .and_then(Client::handle_response)
+            .and_then(|response| {
+                let status = response.status();
+                Client::parse_response_body(response).map(|body| ApiResponse { status, body })
+            })
+    }
+
+    // Ensure that credentials are only sent to the configured API host
+    fn check_host(&self, url: &str) -> MMCResult<()> {
+        if self.allow_foreign_hosts {
+            return Ok(());
+        }
+
+        match (Url::parse(self.base.as_str()), Url::parse(url)) {
+            (Ok(ref base), Ok(ref target)) if base.origin() == target.origin() => Ok(()),
+            _ => Err(MMCError::ForeignHost(url.to_string())),
+        }
+    }
+
+    fn serialize_body<T: Serialize>(body: &T) -> MMCResult<String> {
+        serde_json::to_string(body).map_err(MMCError::Serialize)
     }
 
     fn build_edit_url(
@@ -463,45 +1178,36 @@ impl Client {
 
     fn format_params(params: Params) -> String {
         if !params.is_empty() {
-            let param_string = params
-                .iter()
-                .map(|&(name, value)| format!("{}={}", name, value))
-                .collect::<Vec<String>>()
-                .join("&");
+            // Names and values are encoded by building the query on a placeholder url
+            let mut placeholder =
+                Url::parse("http://localhost/").expect("Placeholder url is valid");
+            placeholder.query_pairs_mut().extend_pairs(params.iter());
 
             let mut args = "?".to_owned();
-            args.push_str(param_string.as_str());
+            args.push_str(placeholder.query().unwrap_or_default());
             args
         } else {
             String::new()
         }
     }
 
-    fn handle_response(response: Response) -> MMCResult<String> {
-        match response.status() {
-            StatusCode::OK | StatusCode::NO_CONTENT => Client::parse_success(response),
-            StatusCode::BAD_REQUEST => Client::parse_bad_request(response),
+    fn handle_response(response: ApiResponse) -> MMCResult<String> {
+        match response.status {
+            StatusCode::OK | StatusCode::NO_CONTENT => Ok(response.body),
+            StatusCode::BAD_REQUEST => Err(MMCError::BadRequest(response.body)),
             StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(MMCError::NotAuthorized),
             StatusCode::NOT_FOUND => Err(MMCError::ResourceNotFound),
             x => Err(MMCError::APIFailure(x)),
         }
     }
 
-    fn parse_success(response: Response) -> MMCResult<String> {
-        Client::parse_response_body(response)
-    }
-
-    fn parse_bad_request(response: Response) -> MMCResult<String> {
-        Client::parse_response_body(response).and_then(|body| Err(MMCError::BadRequest(body)))
-    }
-
     fn parse_response_body(mut response: Response) -> MMCResult<String> {
         // Create a buffer to read the response stream into
         let mut buffer = Vec::new();
 
         // Try to read the response into the buffer and return with a
         // io error in the case of a failure
-        r#try!(response.read_to_end(&mut buffer).map_err(MMCError::Io));
+        response.read_to_end(&mut buffer).map_err(MMCError::Io)?;
 
         // Generate a string from the buffer
         let result = String::from_utf8(buffer);
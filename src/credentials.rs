@@ -0,0 +1,285 @@
+extern crate zeroize;
+
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str;
+
+use crate::error::{MMCError, MMCResult};
+
+/// A Media Manager API key and secret pair
+///
+/// The secret is never included in `Debug` output and both values are zeroed out in memory when
+/// the credentials are dropped.
+#[derive(Clone)]
+pub struct Credentials {
+    key: String,
+    secret: String,
+}
+
+impl Credentials {
+    /// Creates a new key and secret pair
+    pub fn new(key: &str, secret: &str) -> Credentials {
+        Credentials {
+            key: key.to_string(),
+            secret: secret.to_string(),
+        }
+    }
+
+    /// The API key
+    pub fn key(&self) -> &str {
+        self.key.as_str()
+    }
+
+    /// The API secret
+    pub fn secret(&self) -> &str {
+        self.secret.as_str()
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("key", &self.key)
+            .field("secret", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl Drop for Credentials {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.secret.zeroize();
+    }
+}
+
+/// The Media Manager API environments that credentials can be issued for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    /// The production API
+    Live,
+
+    /// The staging API
+    Staging,
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let string_form = match *self {
+            Environment::Live => "live",
+            Environment::Staging => "staging",
+        };
+
+        write!(f, "{}", string_form)
+    }
+}
+
+impl str::FromStr for Environment {
+    type Err = MMCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "live" | "production" => Ok(Environment::Live),
+            "staging" => Ok(Environment::Staging),
+            x => Err(MMCError::UnknownEnvironment(x.to_string())),
+        }
+    }
+}
+
+/// A source of [Credentials](struct.Credentials.html) for a [Client](struct.Client.html)
+pub trait CredentialProvider {
+    /// Attempts to look up the credentials for the requested environment
+    fn credentials(&self, environment: Environment) -> MMCResult<Credentials>;
+}
+
+/// Reads credentials from environment variables
+///
+/// With the default `MM` prefix, production credentials are read from `MM_KEY` and `MM_SECRET`
+/// and staging credentials are read from `MM_STAGING_KEY` and `MM_STAGING_SECRET`.
+#[derive(Clone, Debug)]
+pub struct EnvVarProvider {
+    prefix: String,
+}
+
+impl EnvVarProvider {
+    /// Creates a provider that reads variables with the `MM` prefix
+    pub fn new() -> EnvVarProvider {
+        EnvVarProvider::with_prefix("MM")
+    }
+
+    /// Creates a provider that reads variables with a custom prefix
+    pub fn with_prefix(prefix: &str) -> EnvVarProvider {
+        EnvVarProvider {
+            prefix: prefix.to_string(),
+        }
+    }
+
+    fn var_name(&self, environment: Environment, name: &str) -> String {
+        match environment {
+            Environment::Live => format!("{}_{}", self.prefix, name),
+            Environment::Staging => format!("{}_STAGING_{}", self.prefix, name),
+        }
+    }
+
+    fn read(&self, environment: Environment, name: &str) -> MMCResult<Zeroizing<String>> {
+        let var = self.var_name(environment, name);
+
+        env::var(var.as_str())
+            .map(Zeroizing::new)
+            .map_err(|_| MMCError::MissingCredentials(var))
+    }
+}
+
+impl Default for EnvVarProvider {
+    fn default() -> EnvVarProvider {
+        EnvVarProvider::new()
+    }
+}
+
+impl CredentialProvider for EnvVarProvider {
+    fn credentials(&self, environment: Environment) -> MMCResult<Credentials> {
+        let key = self.read(environment, "KEY")?;
+        let secret = self.read(environment, "SECRET")?;
+
+        Ok(Credentials::new(key.as_str(), secret.as_str()))
+    }
+}
+
+#[derive(Deserialize)]
+struct CredentialsFile {
+    live: Option<KeySecret>,
+    staging: Option<KeySecret>,
+}
+
+#[derive(Deserialize)]
+struct KeySecret {
+    key: String,
+    secret: String,
+}
+
+impl Drop for KeySecret {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.secret.zeroize();
+    }
+}
+
+/// Reads credentials from a TOML or JSON file
+///
+/// The file has optional `live` and `staging` tables that each contain a `key` and `secret`, in
+/// the same layout as the `config.toml` used by `mm_cli`. Other fields are ignored. Files whose
+/// name ends in `.json` are read as JSON and all others as TOML.
+///
+/// ```text
+/// [live]
+/// key = "LIVE_KEY"
+/// secret = "LIVE_SECRET"
+///
+/// [staging]
+/// key = "STAGING_KEY"
+/// secret = "STAGING_SECRET"
+/// ```
+///
+/// A file that does not exist has no credentials, so that a
+/// [ChainProvider](struct.ChainProvider.html) moves on to its next provider, while a file that
+/// can not be read or parsed fails with `MMCError::InvalidCredentials`.
+#[derive(Clone, Debug)]
+pub struct FileProvider {
+    path: PathBuf,
+}
+
+impl FileProvider {
+    /// Creates a provider that reads from the file at the given path
+    pub fn new<P: AsRef<Path>>(path: P) -> FileProvider {
+        FileProvider {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl FileProvider {
+    fn invalid<E: fmt::Display>(&self, err: E) -> MMCError {
+        MMCError::InvalidCredentials(format!("{}. {}", self.path.display(), err))
+    }
+}
+
+impl CredentialProvider for FileProvider {
+    fn credentials(&self, environment: Environment) -> MMCResult<Credentials> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => Zeroizing::new(contents),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(MMCError::MissingCredentials(format!(
+                    "{}, which does not exist",
+                    self.path.display()
+                )))
+            }
+            Err(err) => return Err(self.invalid(err)),
+        };
+
+        let file: CredentialsFile =
+            if self.path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+                serde_json::from_str(contents.as_str()).map_err(|err| self.invalid(err))?
+            } else {
+                toml::from_str(contents.as_str()).map_err(|err| self.invalid(err))?
+            };
+
+        let pair = match environment {
+            Environment::Live => file.live,
+            Environment::Staging => file.staging,
+        };
+
+        pair.map(|pair| Credentials::new(pair.key.as_str(), pair.secret.as_str()))
+            .ok_or_else(|| {
+                MMCError::MissingCredentials(format!("{} in {}", environment, self.path.display()))
+            })
+    }
+}
+
+/// Tries a list of providers in order, returning the first credentials found
+///
+/// Providers that have no credentials for the environment are skipped, and if none of them do
+/// the error lists where credentials were looked for. Any other error from a provider, such as a
+/// file that can not be parsed, is returned immediately.
+#[derive(Default)]
+pub struct ChainProvider {
+    providers: Vec<Box<dyn CredentialProvider>>,
+}
+
+impl ChainProvider {
+    /// Creates an empty chain
+    pub fn new() -> ChainProvider {
+        ChainProvider::default()
+    }
+
+    /// Adds a provider to the end of the chain
+    pub fn with<P: CredentialProvider + 'static>(mut self, provider: P) -> ChainProvider {
+        self.providers.push(Box::new(provider));
+        self
+    }
+}
+
+impl CredentialProvider for ChainProvider {
+    fn credentials(&self, environment: Environment) -> MMCResult<Credentials> {
+        let mut sources = vec![];
+
+        for provider in self.providers.iter() {
+            match provider.credentials(environment) {
+                Ok(credentials) => return Ok(credentials),
+                Err(MMCError::MissingCredentials(source)) => sources.push(source),
+                Err(err) => return Err(err),
+            }
+        }
+
+        if sources.is_empty() {
+            sources.push(environment.to_string());
+        }
+
+        Err(MMCError::MissingCredentials(sources.join(" or ")))
+    }
+}
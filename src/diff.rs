@@ -0,0 +1,113 @@
+use serde_json::{Map, Value};
+
+use crate::error::{MMCError, MMCResult};
+
+/// The attributes that differ between two representations of a Media Manager object
+///
+/// Both representations may either be full API documents (`{"data": {"attributes": {...}}}`),
+/// resource objects (`{"attributes": {...}}`) or bare attribute maps. Attributes that are missing
+/// from the desired representation are left unchanged rather than being removed, but a
+/// representation with no attributes at all is refused.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttributeDiff {
+    /// The attributes whose desired value differs from the current value, mapped to the desired
+    /// value
+    pub changed: Map<String, Value>,
+}
+
+impl AttributeDiff {
+    /// Computes the attributes of `desired` that differ from `current`
+    pub fn between(current: &Value, desired: &Value) -> MMCResult<AttributeDiff> {
+        let current_attrs = attributes_of(current)?;
+
+        let changed = attributes_of(desired)?
+            .iter()
+            .filter(|&(name, value)| current_attrs.get(name) != Some(value))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        Ok(AttributeDiff { changed })
+    }
+
+    /// Returns true when there are no changed attributes
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+    }
+
+    /// Returns the names of the changed attributes
+    pub fn names(&self) -> Vec<&str> {
+        self.changed.keys().map(String::as_str).collect()
+    }
+
+    /// Returns the names of the changed attributes whose value in `current` differs from their
+    /// value in `baseline`, meaning that they have been changed by someone else since the
+    /// baseline was taken
+    pub fn conflicts(&self, baseline: &Value, current: &Value) -> MMCResult<Vec<String>> {
+        let baseline_attrs = attributes_of(baseline)?;
+        let current_attrs = attributes_of(current)?;
+
+        Ok(self
+            .changed
+            .keys()
+            .filter(|name| baseline_attrs.get(*name) != current_attrs.get(*name))
+            .cloned()
+            .collect())
+    }
+
+    /// Builds a JSON:API update document containing only the changed attributes
+    pub fn patch_body(&self, object_type: &str, id: &str) -> Value {
+        serde_json::json!({
+            "data": {
+                "type": object_type,
+                "id": id,
+                "attributes": self.changed,
+            }
+        })
+    }
+}
+
+/// How [Client::update_changed_since](struct.Client.html#method.update_changed_since) handles
+/// attributes that have changed on the server since the baseline was taken
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Sends nothing and fails with a conflict error
+    Refuse,
+
+    /// Sends the update anyway, reporting the conflicting attributes in the result
+    Warn,
+}
+
+/// The result of an update made with
+/// [Client::update_changed](struct.Client.html#method.update_changed)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangedUpdate {
+    /// The attributes that were sent
+    pub diff: AttributeDiff,
+
+    /// The sent attributes that had been changed on the server since the baseline was taken
+    pub conflicts: Vec<String>,
+
+    /// The response to the update, or `None` when there was nothing to change and so no update
+    /// was sent
+    pub response: Option<String>,
+}
+
+/// Returns the attribute map of a Media Manager object in any of the forms accepted by
+/// [AttributeDiff](struct.AttributeDiff.html)
+///
+/// A value is only read as a bare attribute map when it has neither a `data` nor an `attributes`
+/// key, so that a document whose resource has no attributes is refused rather than being read as
+/// an attribute named `data`.
+pub fn attributes_of(value: &Value) -> MMCResult<&Map<String, Value>> {
+    let attributes = match value.get("data") {
+        Some(data) => data.get("attributes"),
+        None => Some(value.get("attributes").unwrap_or(value)),
+    };
+
+    attributes.and_then(Value::as_object).ok_or_else(|| {
+        MMCError::InvalidDocument(
+            "Expected a document, resource object or attribute map with an object of attributes"
+                .to_string(),
+        )
+    })
+}
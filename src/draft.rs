@@ -0,0 +1,747 @@
+use chrono::NaiveDate;
+use reqwest::Url;
+use serde::{Serialize, Serializer};
+
+use std::collections::BTreeMap;
+
+use crate::availability::{Audience, AvailabilityWindow};
+use crate::client::Endpoints;
+use crate::error::{MMCError, MMCResult};
+
+/// The longest `description_short` accepted by the Media Manager API, in characters
+pub const DESCRIPTION_SHORT_MAX: usize = 90;
+
+/// The longest `description_long` accepted by the Media Manager API, in characters
+pub const DESCRIPTION_LONG_MAX: usize = 400;
+
+/// A typed request body for creating or editing an object, which can be checked before it is sent
+///
+/// Drafts serialize to the JSON:API document expected by the create and edit endpoints, including
+/// only the attributes that have been set. A draft made with `new` creates an object and must
+/// contain every attribute required for creation, while a draft made with `edit` updates an
+/// existing object and may contain any subset of attributes.
+pub trait Draft: Serialize {
+    /// The endpoint of the object being created or edited
+    fn endpoint(&self) -> Endpoints;
+
+    /// The endpoint of the parent that new objects are created beneath
+    fn parent(&self) -> Endpoints;
+
+    /// The id of the object being edited, or `None` for a draft that creates an object
+    fn id(&self) -> Option<&str>;
+
+    /// Checks that required attributes are present and that attributes are within the limits
+    /// accepted by the API
+    fn validate(&self) -> MMCResult<()>;
+}
+
+// The JSON:API document that drafts are wrapped in
+#[derive(Serialize)]
+struct Document<'a, A: Serialize> {
+    data: Resource<'a, A>,
+}
+
+#[derive(Serialize)]
+struct Resource<'a, A: Serialize> {
+    #[serde(rename = "type")]
+    _type: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<&'a str>,
+    attributes: &'a A,
+}
+
+fn document<'a, A: Serialize, S: Serializer>(
+    endpoint: Endpoints,
+    id: Option<&'a str>,
+    attributes: &'a A,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    Document {
+        data: Resource {
+            _type: endpoint.singular().as_str(),
+            id,
+            attributes,
+        },
+    }
+    .serialize(serializer)
+}
+
+fn invalid(endpoint: &Endpoints, reason: String) -> MMCError {
+    MMCError::InvalidDraft(format!("{} {}", endpoint.singular(), reason))
+}
+
+fn require<T>(endpoint: &Endpoints, value: &Option<T>, name: &str) -> MMCResult<()> {
+    match *value {
+        Some(_) => Ok(()),
+        None => Err(invalid(endpoint, format!("requires a {}", name))),
+    }
+}
+
+fn check_length(
+    endpoint: &Endpoints,
+    value: &Option<String>,
+    name: &str,
+    max: usize,
+) -> MMCResult<()> {
+    match *value {
+        Some(ref value) if value.trim().is_empty() => {
+            Err(invalid(endpoint, format!("{} can not be empty", name)))
+        }
+        Some(ref value) if value.chars().count() > max => Err(invalid(
+            endpoint,
+            format!("{} can not be longer than {} characters", name, max),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn check_ordinal(endpoint: &Endpoints, ordinal: Option<u32>) -> MMCResult<()> {
+    match ordinal {
+        Some(0) => Err(invalid(
+            endpoint,
+            "ordinal must be 1 or greater".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+struct SeasonAttributes {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ordinal: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description_short: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description_long: Option<String>,
+}
+
+/// A [Draft](trait.Draft.html) for creating a season beneath a show, or editing a season
+///
+/// Creating a season requires an ordinal.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SeasonDraft {
+    id: Option<String>,
+    attributes: SeasonAttributes,
+}
+
+impl SeasonDraft {
+    /// Starts a draft for a new season with the supplied ordinal
+    pub fn new(ordinal: u32) -> SeasonDraft {
+        SeasonDraft::default().with_ordinal(ordinal)
+    }
+
+    /// Starts a draft for editing the season with the supplied id
+    pub fn edit(id: &str) -> SeasonDraft {
+        SeasonDraft {
+            id: Some(id.to_string()),
+            ..SeasonDraft::default()
+        }
+    }
+
+    /// Sets the position of the season within its show, starting from 1
+    pub fn with_ordinal(mut self, ordinal: u32) -> SeasonDraft {
+        self.attributes.ordinal = Some(ordinal);
+        self
+    }
+
+    /// Sets the title of the season
+    pub fn with_title(mut self, title: &str) -> SeasonDraft {
+        self.attributes.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets the short description, of at most [DESCRIPTION_SHORT_MAX](constant.DESCRIPTION_SHORT_MAX.html)
+    /// characters
+    pub fn with_description_short(mut self, description: &str) -> SeasonDraft {
+        self.attributes.description_short = Some(description.to_string());
+        self
+    }
+
+    /// Sets the long description, of at most [DESCRIPTION_LONG_MAX](constant.DESCRIPTION_LONG_MAX.html)
+    /// characters
+    pub fn with_description_long(mut self, description: &str) -> SeasonDraft {
+        self.attributes.description_long = Some(description.to_string());
+        self
+    }
+}
+
+impl Serialize for SeasonDraft {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        document(self.endpoint(), self.id(), &self.attributes, serializer)
+    }
+}
+
+impl Draft for SeasonDraft {
+    fn endpoint(&self) -> Endpoints {
+        Endpoints::Season
+    }
+
+    fn parent(&self) -> Endpoints {
+        Endpoints::Show
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn validate(&self) -> MMCResult<()> {
+        let endpoint = self.endpoint();
+        let attributes = &self.attributes;
+
+        if self.id.is_none() {
+            require(&endpoint, &attributes.ordinal, "ordinal")?;
+        }
+
+        check_ordinal(&endpoint, attributes.ordinal)?;
+        check_length(&endpoint, &attributes.title, "title", usize::MAX)?;
+        check_length(
+            &endpoint,
+            &attributes.description_short,
+            "description_short",
+            DESCRIPTION_SHORT_MAX,
+        )?;
+        check_length(
+            &endpoint,
+            &attributes.description_long,
+            "description_long",
+            DESCRIPTION_LONG_MAX,
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+struct EpisodeAttributes {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ordinal: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description_short: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description_long: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    premiered_on: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encored_on: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nola: Option<String>,
+}
+
+/// A [Draft](trait.Draft.html) for creating an episode beneath a season, or editing an episode
+///
+/// Creating an episode requires a title, ordinal, short description and long description.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EpisodeDraft {
+    id: Option<String>,
+    attributes: EpisodeAttributes,
+}
+
+impl EpisodeDraft {
+    /// Starts a draft for a new episode with the supplied title and ordinal
+    pub fn new(title: &str, ordinal: u32) -> EpisodeDraft {
+        EpisodeDraft::default()
+            .with_title(title)
+            .with_ordinal(ordinal)
+    }
+
+    /// Starts a draft for editing the episode with the supplied id
+    pub fn edit(id: &str) -> EpisodeDraft {
+        EpisodeDraft {
+            id: Some(id.to_string()),
+            ..EpisodeDraft::default()
+        }
+    }
+
+    /// Sets the title of the episode
+    pub fn with_title(mut self, title: &str) -> EpisodeDraft {
+        self.attributes.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets the slug used in urls for the episode
+    pub fn with_slug(mut self, slug: &str) -> EpisodeDraft {
+        self.attributes.slug = Some(slug.to_string());
+        self
+    }
+
+    /// Sets the position of the episode within its season, starting from 1
+    pub fn with_ordinal(mut self, ordinal: u32) -> EpisodeDraft {
+        self.attributes.ordinal = Some(ordinal);
+        self
+    }
+
+    /// Sets the short description, of at most [DESCRIPTION_SHORT_MAX](constant.DESCRIPTION_SHORT_MAX.html)
+    /// characters
+    pub fn with_description_short(mut self, description: &str) -> EpisodeDraft {
+        self.attributes.description_short = Some(description.to_string());
+        self
+    }
+
+    /// Sets the long description, of at most [DESCRIPTION_LONG_MAX](constant.DESCRIPTION_LONG_MAX.html)
+    /// characters
+    pub fn with_description_long(mut self, description: &str) -> EpisodeDraft {
+        self.attributes.description_long = Some(description.to_string());
+        self
+    }
+
+    /// Sets the date the episode first aired
+    pub fn with_premiered_on(mut self, date: NaiveDate) -> EpisodeDraft {
+        self.attributes.premiered_on = Some(date);
+        self
+    }
+
+    /// Sets the date the episode was last re-aired
+    pub fn with_encored_on(mut self, date: NaiveDate) -> EpisodeDraft {
+        self.attributes.encored_on = Some(date);
+        self
+    }
+
+    /// Sets the language of the episode as a two letter code, such as `en`
+    pub fn with_language(mut self, language: &str) -> EpisodeDraft {
+        self.attributes.language = Some(language.to_string());
+        self
+    }
+
+    /// Sets the NOLA code of the episode
+    pub fn with_nola(mut self, nola: &str) -> EpisodeDraft {
+        self.attributes.nola = Some(nola.to_string());
+        self
+    }
+}
+
+impl Serialize for EpisodeDraft {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        document(self.endpoint(), self.id(), &self.attributes, serializer)
+    }
+}
+
+impl Draft for EpisodeDraft {
+    fn endpoint(&self) -> Endpoints {
+        Endpoints::Episode
+    }
+
+    fn parent(&self) -> Endpoints {
+        Endpoints::Season
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn validate(&self) -> MMCResult<()> {
+        let endpoint = self.endpoint();
+        let attributes = &self.attributes;
+
+        if self.id.is_none() {
+            require(&endpoint, &attributes.title, "title")?;
+            require(&endpoint, &attributes.ordinal, "ordinal")?;
+            require(
+                &endpoint,
+                &attributes.description_short,
+                "description_short",
+            )?;
+            require(&endpoint, &attributes.description_long, "description_long")?;
+        }
+
+        check_ordinal(&endpoint, attributes.ordinal)?;
+        check_length(&endpoint, &attributes.title, "title", usize::MAX)?;
+        check_length(&endpoint, &attributes.slug, "slug", usize::MAX)?;
+        check_length(
+            &endpoint,
+            &attributes.description_short,
+            "description_short",
+            DESCRIPTION_SHORT_MAX,
+        )?;
+        check_length(
+            &endpoint,
+            &attributes.description_long,
+            "description_long",
+            DESCRIPTION_LONG_MAX,
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+struct SpecialAttributes {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description_short: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description_long: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    premiered_on: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encored_on: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nola: Option<String>,
+}
+
+/// A [Draft](trait.Draft.html) for creating a special beneath a show, or editing a special
+///
+/// Creating a special requires a title, short description and long description.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpecialDraft {
+    id: Option<String>,
+    attributes: SpecialAttributes,
+}
+
+impl SpecialDraft {
+    /// Starts a draft for a new special with the supplied title
+    pub fn new(title: &str) -> SpecialDraft {
+        SpecialDraft::default().with_title(title)
+    }
+
+    /// Starts a draft for editing the special with the supplied id
+    pub fn edit(id: &str) -> SpecialDraft {
+        SpecialDraft {
+            id: Some(id.to_string()),
+            ..SpecialDraft::default()
+        }
+    }
+
+    /// Sets the title of the special
+    pub fn with_title(mut self, title: &str) -> SpecialDraft {
+        self.attributes.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets the slug used in urls for the special
+    pub fn with_slug(mut self, slug: &str) -> SpecialDraft {
+        self.attributes.slug = Some(slug.to_string());
+        self
+    }
+
+    /// Sets the short description, of at most [DESCRIPTION_SHORT_MAX](constant.DESCRIPTION_SHORT_MAX.html)
+    /// characters
+    pub fn with_description_short(mut self, description: &str) -> SpecialDraft {
+        self.attributes.description_short = Some(description.to_string());
+        self
+    }
+
+    /// Sets the long description, of at most [DESCRIPTION_LONG_MAX](constant.DESCRIPTION_LONG_MAX.html)
+    /// characters
+    pub fn with_description_long(mut self, description: &str) -> SpecialDraft {
+        self.attributes.description_long = Some(description.to_string());
+        self
+    }
+
+    /// Sets the date the special first aired
+    pub fn with_premiered_on(mut self, date: NaiveDate) -> SpecialDraft {
+        self.attributes.premiered_on = Some(date);
+        self
+    }
+
+    /// Sets the date the special was last re-aired
+    pub fn with_encored_on(mut self, date: NaiveDate) -> SpecialDraft {
+        self.attributes.encored_on = Some(date);
+        self
+    }
+
+    /// Sets the language of the special as a two letter code, such as `en`
+    pub fn with_language(mut self, language: &str) -> SpecialDraft {
+        self.attributes.language = Some(language.to_string());
+        self
+    }
+
+    /// Sets the NOLA code of the special
+    pub fn with_nola(mut self, nola: &str) -> SpecialDraft {
+        self.attributes.nola = Some(nola.to_string());
+        self
+    }
+}
+
+impl Serialize for SpecialDraft {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        document(self.endpoint(), self.id(), &self.attributes, serializer)
+    }
+}
+
+impl Draft for SpecialDraft {
+    fn endpoint(&self) -> Endpoints {
+        Endpoints::Special
+    }
+
+    fn parent(&self) -> Endpoints {
+        Endpoints::Show
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn validate(&self) -> MMCResult<()> {
+        let endpoint = self.endpoint();
+        let attributes = &self.attributes;
+
+        if self.id.is_none() {
+            require(&endpoint, &attributes.title, "title")?;
+            require(
+                &endpoint,
+                &attributes.description_short,
+                "description_short",
+            )?;
+            require(&endpoint, &attributes.description_long, "description_long")?;
+        }
+
+        check_length(&endpoint, &attributes.title, "title", usize::MAX)?;
+        check_length(&endpoint, &attributes.slug, "slug", usize::MAX)?;
+        check_length(
+            &endpoint,
+            &attributes.description_short,
+            "description_short",
+            DESCRIPTION_SHORT_MAX,
+        )?;
+        check_length(
+            &endpoint,
+            &attributes.description_long,
+            "description_long",
+            DESCRIPTION_LONG_MAX,
+        )
+    }
+}
+
+fn check_source(endpoint: &Endpoints, source: &str, name: &str) -> MMCResult<()> {
+    match Url::parse(source) {
+        Ok(ref url) if url.scheme() == "http" || url.scheme() == "https" => Ok(()),
+        _ => Err(invalid(
+            endpoint,
+            format!("{} source {} is not an http or https url", name, source),
+        )),
+    }
+}
+
+/// The kinds of asset that can be created
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetType {
+    /// A complete episode or special
+    FullLength,
+
+    /// An excerpt
+    Clip,
+
+    /// A promotional preview
+    Preview,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct VideoSource {
+    profile: String,
+    source: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct CaptionSource {
+    source: String,
+    language: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct ImageSource {
+    profile: String,
+    source: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct AssetAttributes {
+    title: String,
+    object_type: AssetType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description_short: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description_long: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    video: Option<VideoSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    caption: Option<CaptionSource>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    images: Vec<ImageSource>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    availabilities: BTreeMap<Audience, AvailabilityWindow>,
+}
+
+/// A typed request body for creating an asset, whose video, caption and images are ingested by
+/// the API from the supplied source urls
+///
+/// Assets are created beneath a parent with
+/// [Client::create_asset](struct.Client.html#method.create_asset), and their ingest can be
+/// followed with [Client::wait_for_draft_ingest](struct.Client.html#method.wait_for_draft_ingest).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetDraft {
+    attributes: AssetAttributes,
+}
+
+impl AssetDraft {
+    /// Starts a draft for a new asset of the supplied type
+    pub fn new(title: &str, object_type: AssetType) -> AssetDraft {
+        AssetDraft {
+            attributes: AssetAttributes {
+                title: title.to_string(),
+                object_type,
+                slug: None,
+                description_short: None,
+                description_long: None,
+                language: None,
+                tags: vec![],
+                video: None,
+                caption: None,
+                images: vec![],
+                availabilities: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Sets the slug used in urls for the asset
+    pub fn with_slug(mut self, slug: &str) -> AssetDraft {
+        self.attributes.slug = Some(slug.to_string());
+        self
+    }
+
+    /// Sets the short description, of at most [DESCRIPTION_SHORT_MAX](constant.DESCRIPTION_SHORT_MAX.html)
+    /// characters
+    pub fn with_description_short(mut self, description: &str) -> AssetDraft {
+        self.attributes.description_short = Some(description.to_string());
+        self
+    }
+
+    /// Sets the long description, of at most [DESCRIPTION_LONG_MAX](constant.DESCRIPTION_LONG_MAX.html)
+    /// characters
+    pub fn with_description_long(mut self, description: &str) -> AssetDraft {
+        self.attributes.description_long = Some(description.to_string());
+        self
+    }
+
+    /// Sets the language of the asset as a two letter code, such as `en`
+    pub fn with_language(mut self, language: &str) -> AssetDraft {
+        self.attributes.language = Some(language.to_string());
+        self
+    }
+
+    /// Adds a tag to the asset
+    pub fn with_tag(mut self, tag: &str) -> AssetDraft {
+        self.attributes.tags.push(tag.to_string());
+        self
+    }
+
+    /// Sets the url the video is ingested from, along with the encoding profile of the source
+    pub fn with_video(mut self, profile: &str, source: &str) -> AssetDraft {
+        self.attributes.video = Some(VideoSource {
+            profile: profile.to_string(),
+            source: source.to_string(),
+        });
+        self
+    }
+
+    /// Sets the url the captions are ingested from, along with their language
+    pub fn with_caption(mut self, source: &str, language: &str) -> AssetDraft {
+        self.attributes.caption = Some(CaptionSource {
+            source: source.to_string(),
+            language: language.to_string(),
+        });
+        self
+    }
+
+    /// Adds an image ingested from the supplied url for the image profile, such as
+    /// `asset-mezzanine-16x9`
+    pub fn with_image(mut self, profile: &str, source: &str) -> AssetDraft {
+        self.attributes.images.push(ImageSource {
+            profile: profile.to_string(),
+            source: source.to_string(),
+        });
+        self
+    }
+
+    /// Sets the window during which the asset is available to an audience
+    pub fn with_availability(
+        mut self,
+        audience: Audience,
+        window: AvailabilityWindow,
+    ) -> AssetDraft {
+        self.attributes.availabilities.insert(audience, window);
+        self
+    }
+
+    /// Checks that the asset has a title, that descriptions are within the limits accepted by the
+    /// API, that sources are http or https urls and that availability windows start before they
+    /// end
+    pub fn validate(&self) -> MMCResult<()> {
+        let endpoint = Endpoints::Asset;
+        let attributes = &self.attributes;
+
+        check_length(
+            &endpoint,
+            &Some(attributes.title.clone()),
+            "title",
+            usize::MAX,
+        )?;
+        check_length(&endpoint, &attributes.slug, "slug", usize::MAX)?;
+        check_length(
+            &endpoint,
+            &attributes.description_short,
+            "description_short",
+            DESCRIPTION_SHORT_MAX,
+        )?;
+        check_length(
+            &endpoint,
+            &attributes.description_long,
+            "description_long",
+            DESCRIPTION_LONG_MAX,
+        )?;
+
+        if let Some(ref video) = attributes.video {
+            check_source(&endpoint, video.source.as_str(), "video")?;
+        }
+
+        if let Some(ref caption) = attributes.caption {
+            check_source(&endpoint, caption.source.as_str(), "caption")?;
+        }
+
+        for image in attributes.images.iter() {
+            check_source(&endpoint, image.source.as_str(), "image")?;
+        }
+
+        attributes
+            .availabilities
+            .iter()
+            .try_for_each(|(audience, window)| window.validate(*audience))
+    }
+}
+
+impl AssetDraft {
+    // The names of the files the API ingests from the draft's sources, with an entry for each
+    // image
+    pub(crate) fn ingested_files(&self) -> Vec<&'static str> {
+        let attributes = &self.attributes;
+
+        attributes
+            .video
+            .iter()
+            .map(|_| "video")
+            .chain(attributes.caption.iter().map(|_| "caption"))
+            .chain(attributes.images.iter().map(|_| "image"))
+            .collect()
+    }
+}
+
+impl Serialize for AssetDraft {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        document(Endpoints::Asset, None, &self.attributes, serializer)
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{MMCError, MMCResult};
+use crate::interceptor::ApiRequest;
+
+/// A request that a [Client](struct.Client.html) in dry-run mode would have sent
+///
+/// Mutating calls on a dry-run client return their planned request serialized as JSON in place of
+/// the API response, which can be parsed back into a `PlannedRequest` with `serde_json`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlannedRequest {
+    /// The HTTP method that would have been used
+    pub method: String,
+
+    /// The fully formed url that would have been requested
+    pub url: String,
+
+    /// The JSON body that would have been sent, if the request has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+impl PlannedRequest {
+    pub(crate) fn from_request(request: &ApiRequest) -> MMCResult<PlannedRequest> {
+        let body = match request.body {
+            Some(ref body) => Some(serde_json::from_str(body).map_err(MMCError::Serialize)?),
+            None => None,
+        };
+
+        Ok(PlannedRequest {
+            method: request.method.to_string(),
+            url: request.url.clone(),
+            body,
+        })
+    }
+}
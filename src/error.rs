@@ -12,6 +12,7 @@ use std::string;
 pub type MMCResult<T> = Result<T, MMCError>;
 
 /// Error type that represents failures from [Client](struct.Client.html)
+#[non_exhaustive]
 #[derive(Debug)]
 pub enum MMCError {
     /// Generated by when a request tries to access an resource it is not authorized for
@@ -40,6 +41,81 @@ pub enum MMCError {
 
     /// Generated when trying to move an object to an unsupported parent
     UnsupportedMoveParent(String),
+
+    /// Generated when a request body can not be serialized to JSON
+    Serialize(serde_json::Error),
+
+    /// Generated when a request would send credentials to a host other than the API host
+    ForeignHost(String),
+
+    /// Generated when a credential provider is unable to supply a key/secret pair
+    MissingCredentials(String),
+
+    /// Generated when a credential provider finds a source of credentials that can not be read
+    InvalidCredentials(String),
+
+    /// Generated when an environment string can not be parsed
+    UnknownEnvironment(String),
+
+    /// Generated when an API response can not be parsed as JSON
+    Parse(serde_json::Error),
+
+    /// Generated when a batch operation is missing a field required by its kind of operation
+    InvalidOperation(String),
+
+    /// Generated when a journal entry can not be found or the change it records can not be undone
+    Undo(String),
+
+    /// Generated when attributes being updated have been changed on the server since the
+    /// baseline the update was based on
+    Conflict(Vec<String>),
+
+    /// Generated when a typed request body is missing a required attribute or has an attribute
+    /// outside of the limits accepted by the API
+    InvalidDraft(String),
+
+    /// Generated when an availability window does not start before it ends
+    InvalidWindow(String),
+
+    /// Generated when an asset fails to ingest, or does not finish ingesting in the time allowed
+    Ingest(String),
+
+    /// Generated when the period covered by a report ends beyond the dates that can be represented
+    InvalidPeriod(String),
+
+    /// Generated when the attributes of a Media Manager object can not be found in a JSON value
+    InvalidDocument(String),
+}
+
+impl MMCError {
+    /// Returns the name of the error variant, suitable for use as a metric label
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            MMCError::NotAuthorized => "NotAuthorized",
+            MMCError::ResourceNotFound => "ResourceNotFound",
+            MMCError::APIFailure(_) => "APIFailure",
+            MMCError::BadRequest(_) => "BadRequest",
+            MMCError::Convert(_) => "Convert",
+            MMCError::Network(_) => "Network",
+            MMCError::Io(_) => "Io",
+            MMCError::UnknownEndpoint(_) => "UnknownEndpoint",
+            MMCError::UnsupportedMoveParent(_) => "UnsupportedMoveParent",
+            MMCError::Serialize(_) => "Serialize",
+            MMCError::ForeignHost(_) => "ForeignHost",
+            MMCError::MissingCredentials(_) => "MissingCredentials",
+            MMCError::InvalidCredentials(_) => "InvalidCredentials",
+            MMCError::UnknownEnvironment(_) => "UnknownEnvironment",
+            MMCError::Parse(_) => "Parse",
+            MMCError::InvalidOperation(_) => "InvalidOperation",
+            MMCError::Undo(_) => "Undo",
+            MMCError::Conflict(_) => "Conflict",
+            MMCError::InvalidDraft(_) => "InvalidDraft",
+            MMCError::InvalidWindow(_) => "InvalidWindow",
+            MMCError::Ingest(_) => "Ingest",
+            MMCError::InvalidPeriod(_) => "InvalidPeriod",
+            MMCError::InvalidDocument(_) => "InvalidDocument",
+        }
+    }
 }
 
 impl fmt::Display for MMCError {
@@ -72,11 +148,48 @@ impl fmt::Display for MMCError {
                 "Unable to create an object move request to a parent of the {} type",
                 endpoint
             ),
+            MMCError::Serialize(ref err) => err.fmt(f),
+            MMCError::ForeignHost(ref url) => write!(
+                f,
+                "Refusing to send credentials to {} as it is not on the configured API host",
+                url
+            ),
+            MMCError::MissingCredentials(ref source) => {
+                write!(f, "Unable to find API credentials from {}", source)
+            }
+            MMCError::InvalidCredentials(ref reason) => {
+                write!(f, "Unable to read API credentials from {}", reason)
+            }
+            MMCError::UnknownEnvironment(ref environment) => write!(
+                f,
+                "Unable to parse the environment {} into an Environment type",
+                environment
+            ),
+            MMCError::Parse(ref err) => err.fmt(f),
+            MMCError::InvalidOperation(ref reason) => {
+                write!(f, "Invalid batch operation. {}", reason)
+            }
+            MMCError::Undo(ref reason) => write!(f, "Unable to undo change. {}", reason),
+            MMCError::Conflict(ref names) => write!(
+                f,
+                "Refusing to update {} as it has been changed on the server since it was fetched",
+                names.join(", ")
+            ),
+            MMCError::InvalidDraft(ref reason) => write!(f, "Invalid request body. The {}", reason),
+            MMCError::InvalidWindow(ref reason) => {
+                write!(f, "Invalid availability window. {}", reason)
+            }
+            MMCError::Ingest(ref reason) => write!(f, "Asset ingest failed. {}", reason),
+            MMCError::InvalidPeriod(ref reason) => write!(f, "Invalid report period. {}", reason),
+            MMCError::InvalidDocument(ref reason) => {
+                write!(f, "Unable to read object attributes. {}", reason)
+            }
         }
     }
 }
 
 impl Error for MMCError {
+    #[allow(deprecated)]
     fn description(&self) -> &str {
         match *self {
             MMCError::NotAuthorized => "Not authorized response from the API",
@@ -88,14 +201,30 @@ impl Error for MMCError {
             MMCError::Io(ref err) => err.description(),
             MMCError::UnknownEndpoint(_) => "Can not parse endpoint into type",
             MMCError::UnsupportedMoveParent(_) => "Unable to create move request",
+            MMCError::Serialize(_) => "Unable to serialize request body",
+            MMCError::ForeignHost(_) => "Refusing to send credentials to a foreign host",
+            MMCError::MissingCredentials(_) => "Unable to find API credentials",
+            MMCError::InvalidCredentials(_) => "Unable to read API credentials",
+            MMCError::UnknownEnvironment(_) => "Can not parse environment into type",
+            MMCError::Parse(_) => "Unable to parse API response",
+            MMCError::InvalidOperation(_) => "Invalid batch operation",
+            MMCError::Undo(_) => "Unable to undo change",
+            MMCError::Conflict(_) => "Attributes have been changed on the server",
+            MMCError::InvalidDraft(_) => "Invalid request body",
+            MMCError::InvalidWindow(_) => "Invalid availability window",
+            MMCError::Ingest(_) => "Asset ingest failed",
+            MMCError::InvalidPeriod(_) => "Invalid report period",
+            MMCError::InvalidDocument(_) => "Unable to read object attributes",
         }
     }
 
-    fn cause(&self) -> Option<&Error> {
+    fn cause(&self) -> Option<&dyn Error> {
         match *self {
             MMCError::Convert(ref err) => Some(err),
             MMCError::Network(ref err) => Some(err),
             MMCError::Io(ref err) => Some(err),
+            MMCError::Serialize(ref err) => Some(err),
+            MMCError::Parse(ref err) => Some(err),
             _ => None,
         }
     }
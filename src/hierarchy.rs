@@ -0,0 +1,90 @@
+use serde_json::Value;
+
+use crate::client::{Client, Endpoints};
+use crate::error::MMCResult;
+
+/// An asset found by [Client::crawl_assets](struct.Client.html#method.crawl_assets), along with
+/// the objects it was found beneath
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrawledAsset {
+    /// The objects between the crawl's starting point and the asset, outermost first, as they
+    /// appeared in their parent's list. The starting object itself is not included.
+    pub ancestors: Vec<(Endpoints, Value)>,
+
+    /// The asset as it appeared in its parent's list
+    pub asset: Value,
+}
+
+impl CrawledAsset {
+    /// Returns the nearest ancestor of the supplied type, if the asset was found beneath one
+    pub fn ancestor(&self, endpoint: &Endpoints) -> Option<&Value> {
+        self.ancestors
+            .iter()
+            .rev()
+            .find(|(ancestor, _)| ancestor == endpoint)
+            .map(|(_, value)| value)
+    }
+}
+
+// The types of object listed beneath each level of the hierarchy, other than assets
+fn children_of(endpoint: &Endpoints) -> Vec<Endpoints> {
+    match *endpoint {
+        Endpoints::Franchise => vec![Endpoints::Show],
+        Endpoints::Show => vec![Endpoints::Season, Endpoints::Special],
+        Endpoints::Season => vec![Endpoints::Episode],
+        _ => vec![],
+    }
+}
+
+impl Client {
+    /// Attempts to find every asset beneath a franchise, show, season, episode or special by
+    /// listing each level of the hierarchy in turn. Assets attached directly to the starting
+    /// object and to each object beneath it are included. Other endpoints have no assets beneath
+    /// them and produce an empty list.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(endpoint = %endpoint, id = id))
+    )]
+    pub fn crawl_assets(&self, endpoint: Endpoints, id: &str) -> MMCResult<Vec<CrawledAsset>> {
+        let mut assets = vec![];
+        self.crawl(endpoint, id, &mut vec![], &mut assets)?;
+
+        Ok(assets)
+    }
+
+    fn crawl(
+        &self,
+        endpoint: Endpoints,
+        id: &str,
+        ancestors: &mut Vec<(Endpoints, Value)>,
+        assets: &mut Vec<CrawledAsset>,
+    ) -> MMCResult<()> {
+        match endpoint {
+            Endpoints::Franchise
+            | Endpoints::Show
+            | Endpoints::Season
+            | Endpoints::Episode
+            | Endpoints::Special => (),
+            _ => return Ok(()),
+        }
+
+        for asset in self.child_list_all(Endpoints::Asset, id, endpoint.clone(), None)? {
+            assets.push(CrawledAsset {
+                ancestors: ancestors.clone(),
+                asset,
+            });
+        }
+
+        for child in children_of(&endpoint) {
+            for item in self.child_list_all(child.clone(), id, endpoint.clone(), None)? {
+                if let Some(child_id) = item.get("id").and_then(Value::as_str).map(String::from) {
+                    ancestors.push((child.clone(), item));
+                    self.crawl(child.clone(), child_id.as_str(), ancestors, assets)?;
+                    ancestors.pop();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use serde_json::Value;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::client::{Client, Endpoints};
+use crate::diff::attributes_of;
+use crate::draft::AssetDraft;
+use crate::error::{MMCError, MMCResult};
+
+/// The progress of the API in ingesting the video, caption and images of an asset from their
+/// source urls
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IngestStatus {
+    /// At least one file is still being ingested
+    InProgress,
+
+    /// Every file has been ingested
+    Done,
+
+    /// A file could not be ingested, with a description of the failure
+    Failed(String),
+}
+
+impl IngestStatus {
+    /// Determines the ingest status of an asset from its edit object. Each ingested file reports
+    /// its own `ingestion_status`, and the asset has finished ingesting once every file is `done`.
+    /// A file without a status has not started ingesting, and an asset without any files is
+    /// treated as not having started either, as the API adds them some time after the asset is
+    /// created.
+    pub fn of(asset: &Value) -> IngestStatus {
+        IngestStatus::expecting(asset, None)
+    }
+
+    /// Works as `of` for an asset created from the supplied draft, additionally treating the
+    /// asset as still ingesting until it has a video, caption and images for each of the sources
+    /// the draft supplied. An asset whose draft supplied no sources has nothing to ingest.
+    pub fn of_draft(asset: &Value, draft: &AssetDraft) -> IngestStatus {
+        IngestStatus::expecting(asset, Some(draft.ingested_files()))
+    }
+
+    fn expecting(asset: &Value, expected: Option<Vec<&str>>) -> IngestStatus {
+        let attributes = match attributes_of(asset) {
+            Ok(attributes) => attributes,
+            Err(_) => return IngestStatus::InProgress,
+        };
+
+        let images = attributes
+            .get("images")
+            .and_then(Value::as_array)
+            .map(|images| images.iter().map(|image| ("image", image)).collect())
+            .unwrap_or_else(Vec::new);
+
+        let files: Vec<(&str, &Value)> = ["video", "caption"]
+            .iter()
+            .filter_map(|name| attributes.get(*name).map(|file| (*name, file)))
+            .filter(|(_, file)| !file.is_null())
+            .chain(images)
+            .collect();
+
+        let mut status = match expected {
+            Some(expected) if missing(&expected, &files) => IngestStatus::InProgress,
+            None if files.is_empty() => IngestStatus::InProgress,
+            _ => IngestStatus::Done,
+        };
+
+        for (name, file) in files {
+            match file.get("ingestion_status").and_then(Value::as_str) {
+                Some("done") => (),
+                Some("failed") => {
+                    let reason = file
+                        .get("ingestion_error")
+                        .and_then(Value::as_str)
+                        .unwrap_or("no reason was given");
+
+                    return IngestStatus::Failed(format!(
+                        "The {} failed to ingest: {}",
+                        name, reason
+                    ));
+                }
+                Some(_) | None => status = IngestStatus::InProgress,
+            }
+        }
+
+        status
+    }
+}
+
+// Whether any of the expected files have yet to appear on the asset
+fn missing(expected: &[&str], files: &[(&str, &Value)]) -> bool {
+    expected.iter().any(|name| {
+        let wanted = expected.iter().filter(|other| *other == name).count();
+        let present = files.iter().filter(|(other, _)| other == name).count();
+
+        present < wanted
+    })
+}
+
+impl Client {
+    /// Attempts to fetch the edit object of an asset and determine its
+    /// [IngestStatus](enum.IngestStatus.html)
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = id)))]
+    pub fn ingest_status(&self, id: &str) -> MMCResult<IngestStatus> {
+        let body = self.edit(Endpoints::Asset, id)?;
+        let parsed: Value = serde_json::from_str(body.as_str()).map_err(MMCError::Parse)?;
+
+        Ok(IngestStatus::of(&parsed))
+    }
+
+    /// Polls the edit object of an asset every `interval` until its files have finished
+    /// ingesting, as determined by [IngestStatus::of](enum.IngestStatus.html#method.of),
+    /// returning the final edit object. Fails with `MMCError::Ingest` if a file fails to ingest
+    /// or ingest has not finished once `timeout` has elapsed.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = id)))]
+    pub fn wait_for_ingest(
+        &self,
+        id: &str,
+        interval: Duration,
+        timeout: Duration,
+    ) -> MMCResult<String> {
+        self.wait_until_ingested(id, interval, timeout, IngestStatus::of)
+    }
+
+    /// Works as `wait_for_ingest` for an asset created from the supplied draft, waiting until a
+    /// file has appeared and finished ingesting for each of the draft's sources
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = id)))]
+    pub fn wait_for_draft_ingest(
+        &self,
+        id: &str,
+        draft: &AssetDraft,
+        interval: Duration,
+        timeout: Duration,
+    ) -> MMCResult<String> {
+        self.wait_until_ingested(id, interval, timeout, |asset| {
+            IngestStatus::of_draft(asset, draft)
+        })
+    }
+
+    fn wait_until_ingested<F>(
+        &self,
+        id: &str,
+        interval: Duration,
+        timeout: Duration,
+        status_of: F,
+    ) -> MMCResult<String>
+    where
+        F: Fn(&Value) -> IngestStatus,
+    {
+        let started = Instant::now();
+
+        loop {
+            let body = self.edit(Endpoints::Asset, id)?;
+            let parsed: Value = serde_json::from_str(body.as_str()).map_err(MMCError::Parse)?;
+
+            match status_of(&parsed) {
+                IngestStatus::Done => return Ok(body),
+                IngestStatus::Failed(reason) => return Err(MMCError::Ingest(reason)),
+                IngestStatus::InProgress if started.elapsed() >= timeout => {
+                    return Err(MMCError::Ingest(format!(
+                        "Asset {} did not finish ingesting within {} seconds",
+                        id,
+                        timeout.as_secs()
+                    )))
+                }
+                IngestStatus::InProgress => thread::sleep(interval),
+            }
+        }
+    }
+}
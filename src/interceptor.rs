@@ -0,0 +1,90 @@
+extern crate reqwest;
+
+use reqwest::{Method, StatusCode};
+
+use std::fmt;
+
+use crate::client::Endpoints;
+
+/// A request that is about to be sent to the Media Manager API
+///
+/// Interceptors are handed a mutable reference to the request prior to it being sent and may
+/// modify any part of it. Authentication is applied after all interceptors have run.
+#[derive(Clone, Debug)]
+pub struct ApiRequest {
+    /// The HTTP method of the request
+    pub method: Method,
+
+    /// The fully formed url, including query parameters
+    pub url: String,
+
+    /// Additional headers to attach to the request
+    pub headers: Vec<(String, String)>,
+
+    /// The serialized JSON body of the request, if it has one
+    pub body: Option<String>,
+
+    /// The endpoint being requested, or `None` for requests made against arbitrary urls
+    pub endpoint: Option<Endpoints>,
+}
+
+impl ApiRequest {
+    /// Creates a new request with no additional headers
+    pub fn new(method: Method, url: &str, body: Option<String>) -> ApiRequest {
+        ApiRequest {
+            method,
+            url: url.to_string(),
+            headers: vec![],
+            body,
+            endpoint: None,
+        }
+    }
+
+    /// Sets the endpoint that the request is made against
+    pub fn for_endpoint(mut self, endpoint: Option<Endpoints>) -> ApiRequest {
+        self.endpoint = endpoint;
+        self
+    }
+}
+
+/// A response received from (or synthesized in place of) the Media Manager API
+#[derive(Clone, Debug)]
+pub struct ApiResponse {
+    /// The HTTP status of the response
+    pub status: StatusCode,
+
+    /// The body of the response
+    pub body: String,
+}
+
+impl ApiResponse {
+    /// Creates a new response with the given status and body
+    pub fn new(status: StatusCode, body: &str) -> ApiResponse {
+        ApiResponse {
+            status,
+            body: body.to_string(),
+        }
+    }
+}
+
+/// A hook into the request path of a [Client](struct.Client.html)
+///
+/// Interceptors are run in the order they were added to the client before a request is sent, and
+/// in the reverse order after a response is received. An interceptor may short-circuit a request
+/// by returning a synthetic [ApiResponse](struct.ApiResponse.html) from `before_send`, in which
+/// case no network request is made and later interceptors are not consulted.
+pub trait Interceptor: Send + Sync {
+    /// Called before a request is sent. Returning a response skips sending the request.
+    fn before_send(&self, _request: &mut ApiRequest) -> Option<ApiResponse> {
+        None
+    }
+
+    /// Called after a response has been received, before it is mapped to a result
+    fn after_response(&self, _request: &ApiRequest, _response: &mut ApiResponse) {}
+}
+
+impl fmt::Debug for dyn Interceptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Interceptor")
+    }
+}
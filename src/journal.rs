@@ -0,0 +1,172 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::batch::Operation;
+use crate::client::Endpoints;
+use crate::diff::attributes_of;
+use crate::error::{MMCError, MMCResult};
+
+/// The state of an object captured by a [Client](struct.Client.html) before it was updated or
+/// deleted, from which the change can be undone
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Identifies the entry within its journal
+    pub id: String,
+
+    /// When the state was captured
+    pub timestamp: DateTime<Utc>,
+
+    /// The change that was made, either an update or a delete
+    pub operation: Operation,
+
+    /// The endpoint of the changed object
+    pub endpoint: Endpoints,
+
+    /// The id of the changed object
+    pub object_id: String,
+
+    /// The endpoint of the parent of a deleted object, when it could be determined
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Endpoints>,
+
+    /// The id of the parent of a deleted object, when it could be determined
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+
+    /// The edit object as it was before the change
+    pub previous: Value,
+
+    /// The body of the update that was sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<Value>,
+}
+
+impl JournalEntry {
+    pub(crate) fn capture(
+        operation: Operation,
+        endpoint: Endpoints,
+        object_id: &str,
+        previous: Value,
+        changes: Option<Value>,
+    ) -> JournalEntry {
+        let timestamp = Utc::now();
+
+        let parent = parent_keys(&endpoint).iter().find_map(|(key, parent)| {
+            let value = attributes_of(&previous).ok()?.get(*key)?;
+            let id = value
+                .as_str()
+                .or_else(|| value.get("id").and_then(Value::as_str))?;
+
+            Some((parent.clone(), id.to_string()))
+        });
+
+        JournalEntry {
+            id: format!("{}-{}", timestamp.format("%Y%m%d%H%M%S%6f"), object_id),
+            timestamp,
+            operation,
+            endpoint,
+            object_id: object_id.to_string(),
+            parent_id: parent.as_ref().map(|(_, id)| id.clone()),
+            parent: parent.map(|(parent, _)| parent),
+            previous,
+            changes,
+        }
+    }
+}
+
+// The attributes that may reference the parent of an object, in order of preference
+fn parent_keys(endpoint: &Endpoints) -> Vec<(&'static str, Endpoints)> {
+    match *endpoint {
+        Endpoints::Asset => vec![
+            ("episode", Endpoints::Episode),
+            ("special", Endpoints::Special),
+            ("season", Endpoints::Season),
+            ("show", Endpoints::Show),
+            ("franchise", Endpoints::Franchise),
+        ],
+        Endpoints::Episode => vec![("season", Endpoints::Season)],
+        Endpoints::Season | Endpoints::Special => vec![("show", Endpoints::Show)],
+        Endpoints::Show => vec![("franchise", Endpoints::Franchise)],
+        _ => vec![],
+    }
+}
+
+/// A store for the [JournalEntry](struct.JournalEntry.html)s captured by a
+/// [Client](struct.Client.html)
+pub trait Journal: Send + Sync {
+    /// Adds an entry to the journal
+    fn append(&self, entry: &JournalEntry) -> MMCResult<()>;
+
+    /// Returns every entry in the journal, oldest first
+    fn entries(&self) -> MMCResult<Vec<JournalEntry>>;
+
+    /// Returns the entry with the supplied id
+    fn entry(&self, id: &str) -> MMCResult<JournalEntry> {
+        self.entries()?
+            .into_iter()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| MMCError::Undo(format!("No journal entry with the id {}", id)))
+    }
+}
+
+impl fmt::Debug for dyn Journal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Journal")
+    }
+}
+
+/// A [Journal](trait.Journal.html) kept in a file with one line of JSON per entry
+#[derive(Debug)]
+pub struct FileJournal {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileJournal {
+    /// Creates a journal backed by the file at the supplied path. The file is created when the
+    /// first entry is added.
+    pub fn new<P: AsRef<Path>>(path: P) -> FileJournal {
+        FileJournal {
+            path: path.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+impl Journal for FileJournal {
+    fn append(&self, entry: &JournalEntry) -> MMCResult<()> {
+        let line = serde_json::to_string(entry).map_err(MMCError::Serialize)?;
+        let _guard = self
+            .lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", line))
+            .map_err(MMCError::Io)
+    }
+
+    fn entries(&self) -> MMCResult<Vec<JournalEntry>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(MMCError::Io(err)),
+        };
+
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(MMCError::Parse))
+            .collect()
+    }
+}
@@ -28,6 +28,20 @@
 //! let client = Client::new("API_KEY", "API_SECRET").unwrap();
 //! ```
 //!
+//! Credentials can also be looked up from a
+//! [CredentialProvider](trait.CredentialProvider.html). Providers are included for reading from
+//! environment variables, from a TOML or JSON file, and for chaining multiple providers together.
+//!
+//! ```no_run
+//! use mm_client::{ChainProvider, Client, EnvVarProvider, Environment, FileProvider};
+//!
+//! let provider = ChainProvider::new()
+//!     .with(EnvVarProvider::new())
+//!     .with(FileProvider::new("/etc/mm/credentials.toml"));
+//!
+//! let client = Client::from_provider(&provider, Environment::Staging).unwrap();
+//! ```
+//!
 //! # Fetching a single object
 //!
 //! Requesting a single object can be performed by using the `get` method
@@ -55,6 +69,280 @@
 //! ```
 //! Here a request is made for all of the show objects that have been updated since the supplied
 //! date. Similar to the `get` method, the response string is available to pass to a JSON parser
+//!
+//! Lists are paginated by the API. The `list_all`, `child_list_all` and `changelog_all` methods
+//! follow the pages of a list until the last page is reached and return the combined `data`
+//! entries.
+//!
+//! ```no_run
+//! use mm_client::Client;
+//! use mm_client::Endpoints;
+//!
+//! let client = Client::new("API_KEY", "API_SECRET").unwrap();
+//! let seasons = client.child_list_all(Endpoints::Season, "show-id", Endpoints::Show, None);
+//! let changes = client.changelog_all(vec![("since", "2020-01-01T00:00:00Z")]);
+//! ```
+//!
+//! # Updating changed attributes
+//!
+//! `update_changed` fetches the edit object and sends only the attributes of the desired state
+//! that differ from it, so that attributes changed by other editors are not overwritten.
+//! `update_changed_since` additionally takes the edit object that the desired state was based on,
+//! and refuses (or warns of) updates to attributes that have changed on the server since.
+//!
+//! ```no_run
+//! use mm_client::{Client, ConflictPolicy, Endpoints};
+//!
+//! let client = Client::new("API_KEY", "API_SECRET").unwrap();
+//! let baseline: serde_json::Value =
+//!     serde_json::from_str(client.edit(Endpoints::Episode, "episode-id").unwrap().as_str()).unwrap();
+//!
+//! let mut desired = baseline.clone();
+//! desired["data"]["attributes"]["title"] = "New title".into();
+//!
+//! let result = client.update_changed_since(
+//!     Endpoints::Episode,
+//!     "episode-id",
+//!     &desired,
+//!     &baseline,
+//!     ConflictPolicy::Refuse,
+//! );
+//! ```
+//!
+//! # Typed request bodies
+//!
+//! [SeasonDraft](struct.SeasonDraft.html), [EpisodeDraft](struct.EpisodeDraft.html) and
+//! [SpecialDraft](struct.SpecialDraft.html) build create and edit bodies attribute by attribute.
+//! `create_draft` and `update_draft` check a draft for missing required attributes and overlong
+//! descriptions before anything is sent, returning `MMCError::InvalidDraft` in place of the
+//! `BadRequest` the API would respond with.
+//!
+//! ```no_run
+//! use chrono::NaiveDate;
+//! use mm_client::{Client, EpisodeDraft};
+//!
+//! let client = Client::new("API_KEY", "API_SECRET").unwrap();
+//!
+//! let episode = EpisodeDraft::new("Pilot", 1)
+//!     .with_description_short("The first episode")
+//!     .with_description_long("The first episode of the first season")
+//!     .with_premiered_on(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap());
+//! let created = client.create_draft("season-id", &episode);
+//!
+//! let retitle = EpisodeDraft::edit("episode-id").with_title("New title");
+//! let updated = client.update_draft(&retitle);
+//! ```
+//!
+//! # Ingesting assets
+//!
+//! An [AssetDraft](struct.AssetDraft.html) describes a new asset along with the urls its video,
+//! captions and images are ingested from. `create_asset` creates it beneath an episode, special,
+//! season, show or franchise, and `wait_for_draft_ingest` polls the asset until a file has been
+//! ingested from each of the draft's sources.
+//!
+//! ```no_run
+//! use mm_client::{AssetDraft, AssetType, Audience, AvailabilityWindow, Client, Endpoints};
+//! use std::time::Duration;
+//!
+//! let client = Client::new("API_KEY", "API_SECRET").unwrap();
+//!
+//! let asset = AssetDraft::new("Pilot", AssetType::FullLength)
+//!     .with_video("hd-1080p-mezzanine-16x9", "https://example.org/pilot.mp4")
+//!     .with_caption("https://example.org/pilot.srt", "en")
+//!     .with_image("asset-mezzanine-16x9", "https://example.org/pilot.jpg")
+//!     .with_availability(
+//!         Audience::Public,
+//!         AvailabilityWindow::new(Some("2020-01-01T00:00:00Z".parse().unwrap()), None),
+//!     );
+//!
+//! let created = client.create_asset(Endpoints::Episode, "episode-id", &asset).unwrap();
+//! let id = serde_json::from_str::<serde_json::Value>(created.as_str()).unwrap()["data"]["id"]
+//!     .as_str()
+//!     .unwrap()
+//!     .to_string();
+//!
+//! let ingested = client.wait_for_draft_ingest(
+//!     id.as_str(),
+//!     &asset,
+//!     Duration::from_secs(10),
+//!     Duration::from_secs(3600),
+//! );
+//! ```
+//!
+//! # Availability windows
+//!
+//! The public, all members and station members windows of an asset can be read with
+//! `availabilities` and changed one audience at a time with `set_availability`,
+//! `extend_availability` and `expire_availability`. Each change is checked to start before it
+//! ends and is sent as a single update of the asset. `shift_availability_beneath` moves every
+//! window of every asset beneath a season or show, found with `crawl_assets`.
+//!
+//! ```no_run
+//! use chrono::{Duration, Utc};
+//! use mm_client::{Audience, Client, Endpoints};
+//!
+//! let client = Client::new("API_KEY", "API_SECRET").unwrap();
+//!
+//! let windows = client.availabilities("asset-id").unwrap();
+//! client.extend_availability("asset-id", Audience::Public, Duration::days(30)).unwrap();
+//! client.expire_availability("asset-id", Audience::StationMembers, Utc::now()).unwrap();
+//!
+//! let results = client.shift_availability_beneath(Endpoints::Season, "season-id", Duration::weeks(1));
+//! ```
+//!
+//! # Playability
+//!
+//! [playability](fn.playability.html) answers whether an [Asset](struct.Asset.html) can be
+//! played by a public, all members or station members viewer in a given country at a given
+//! instant, using only its publish state, geo profile and availability windows. When it can not,
+//! the reason is returned.
+//!
+//! ```no_run
+//! use chrono::Utc;
+//! use mm_client::{playability, Asset, Audience, Client, Endpoints, Playability};
+//!
+//! let client = Client::new("API_KEY", "API_SECRET").unwrap();
+//! let body = client.edit(Endpoints::Asset, "asset-id").unwrap();
+//! let asset = Asset::from_value(&serde_json::from_str(body.as_str()).unwrap()).unwrap();
+//!
+//! match playability(&asset, Audience::AllMembers, "US", Utc::now()) {
+//!     Playability::Playable => println!("Playable"),
+//!     Playability::Unplayable(reason) => println!("{}", reason),
+//! }
+//! ```
+//!
+//! # Batch operations
+//!
+//! A [BatchExecutor](struct.BatchExecutor.html) applies a list of creates, updates, deletes and
+//! moves with a bounded number of requests in flight at once. Every operation is attempted even
+//! when others fail, and an outcome is returned for each operation in the order they were given.
+//! Operations can be deserialized from JSON such as
+//! `{"operation": "delete", "endpoint": "episodes", "id": "episode-id"}`.
+//!
+//! ```no_run
+//! use mm_client::{BatchExecutor, BatchOperation, Client, Endpoints, Operation};
+//!
+//! let client = Client::new("API_KEY", "API_SECRET").unwrap();
+//! let operations = vec![BatchOperation {
+//!     operation: Operation::Update,
+//!     endpoint: Endpoints::Episode,
+//!     id: Some("episode-id".to_string()),
+//!     parent: None,
+//!     parent_id: None,
+//!     body: Some(serde_json::json!({"data": {"attributes": {"title": "New title"}}})),
+//! }];
+//!
+//! let outcomes = BatchExecutor::new(&client).with_concurrency(8).run(&operations);
+//! ```
+//!
+//! # Dry runs
+//!
+//! A client in dry-run mode builds and validates every create, update, delete and move without
+//! sending it. Each of those calls returns the [PlannedRequest](struct.PlannedRequest.html) as
+//! JSON in place of the API response, so that changes can be reviewed before they are made. Dry
+//! runs apply to batches run with the client as well.
+//!
+//! ```no_run
+//! use mm_client::{Client, Endpoints, PlannedRequest};
+//!
+//! let client = Client::new("API_KEY", "API_SECRET").unwrap().dry_run(true);
+//! let response = client.delete(Endpoints::Episode, "episode-id").unwrap();
+//! let plan: PlannedRequest = serde_json::from_str(response.as_str()).unwrap();
+//!
+//! assert_eq!(plan.method, "DELETE");
+//! ```
+//!
+//! # Intercepting requests
+//!
+//! An [Interceptor](trait.Interceptor.html) can be added to a [Client](struct.Client.html) to
+//! inspect or modify every request before it is sent and every response after it is received.
+//! Interceptors can also short-circuit a request by returning a synthetic response.
+//!
+//! ```no_run
+//! use mm_client::{ApiRequest, Client, Interceptor};
+//!
+//! struct AddHeader;
+//!
+//! impl Interceptor for AddHeader {
+//!     fn before_send(&self, request: &mut ApiRequest) -> Option<mm_client::ApiResponse> {
+//!         request.headers.push(("X-Station".to_string(), "tpt".to_string()));
+//!         None
+//!     }
+//! }
+//!
+//! let client = Client::new("API_KEY", "API_SECRET")
+//!     .unwrap()
+//!     .with_interceptor(AddHeader);
+//! ```
+//!
+//! # Collecting metrics
+//!
+//! A [MetricsSink](trait.MetricsSink.html) can be attached to a [Client](struct.Client.html) to
+//! record request counts, error counts, retries and latency for every request, keyed by endpoint
+//! and HTTP method. Retries are only made by clients configured with `with_retries`.
+//! [InMemoryMetrics](struct.InMemoryMetrics.html) aggregates these in process and can be
+//! snapshotted at any time for export.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use mm_client::{Client, InMemoryMetrics};
+//!
+//! let metrics = Arc::new(InMemoryMetrics::new());
+//! let client = Client::new("API_KEY", "API_SECRET")
+//!     .unwrap()
+//!     .with_metrics(metrics.clone());
+//!
+//! let _ = client.show("show-id", None);
+//! let snapshot = metrics.snapshot();
+//! ```
+//!
+//! # Auditing changes
+//!
+//! An [AuditSink](trait.AuditSink.html) can be attached to a [Client](struct.Client.html) to keep
+//! a record of every create, update, delete and move it sends, including the operator label, the
+//! method, url and body of the request, the response status and the id of the changed object.
+//! [JsonLinesAudit](struct.JsonLinesAudit.html) appends each record to a file or any other writer
+//! as a line of JSON. Requests planned by a dry run are not sent and so are not audited.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use mm_client::{Client, JsonLinesAudit};
+//!
+//! let audit = Arc::new(JsonLinesAudit::open("audit.jsonl").unwrap());
+//! let client = Client::new("API_KEY", "API_SECRET")
+//!     .unwrap()
+//!     .with_audit(audit, "editor@example.com");
+//! ```
+//!
+//! # Undoing changes
+//!
+//! A [Journal](trait.Journal.html) can be attached to a [Client](struct.Client.html) so that the
+//! edit object of every object is captured before it is updated or deleted, and kept once the
+//! change has been accepted. [FileJournal](struct.FileJournal.html) keeps these entries in a file as lines of JSON. Passing
+//! an entry to `undo` restores the previous values of the attributes that an update changed, or
+//! recreates a deleted object beneath its parent.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use mm_client::{Client, Endpoints, FileJournal, Journal};
+//!
+//! let journal = Arc::new(FileJournal::new("journal.jsonl"));
+//! let client = Client::new("API_KEY", "API_SECRET")
+//!     .unwrap()
+//!     .with_journal(journal.clone());
+//!
+//! let _ = client.delete(Endpoints::Episode, "episode-id");
+//!
+//! let entries = journal.entries().unwrap();
+//! let _ = client.undo(&entries[0]);
+//! ```
+//!
+//! # Tracing
+//!
+//! When the `tracing` feature is enabled every public request method of the
+//! [Client](struct.Client.html) is wrapped in a span recording the endpoint and id being accessed.
+//! The underlying HTTP request is wrapped in a nested span that records the method, url, response
+//! status, response size in bytes and latency. API credentials are never recorded.
 
 #![deny(missing_docs)]
 #[cfg(test)]
@@ -68,26 +356,128 @@ extern crate serde_json;
 #[cfg(test)]
 extern crate uuid;
 
+mod asset;
+mod audit;
+mod availability;
+mod batch;
 mod client;
+mod credentials;
+mod diff;
+mod draft;
+mod dry_run;
 mod error;
+mod hierarchy;
+mod ingest;
+mod interceptor;
+mod journal;
+mod metrics;
+mod pagination;
+mod playability;
+mod report;
+pub use crate::asset::Asset;
+pub use crate::asset::GeoProfile;
+pub use crate::audit::AuditRecord;
+pub use crate::audit::AuditSink;
+pub use crate::audit::JsonLinesAudit;
+pub use crate::availability::availabilities_of;
+pub use crate::availability::Audience;
+pub use crate::availability::Availabilities;
+pub use crate::availability::AvailabilityWindow;
+pub use crate::batch::BatchExecutor;
+pub use crate::batch::BatchOperation;
+pub use crate::batch::BatchOutcome;
+pub use crate::batch::Operation;
+pub use crate::batch::DEFAULT_CONCURRENCY;
 pub use crate::client::Client;
 pub use crate::client::Endpoints;
+pub use crate::credentials::ChainProvider;
+pub use crate::credentials::CredentialProvider;
+pub use crate::credentials::Credentials;
+pub use crate::credentials::EnvVarProvider;
+pub use crate::credentials::Environment;
+pub use crate::credentials::FileProvider;
+pub use crate::diff::attributes_of;
+pub use crate::diff::AttributeDiff;
+pub use crate::diff::ChangedUpdate;
+pub use crate::diff::ConflictPolicy;
+pub use crate::draft::AssetDraft;
+pub use crate::draft::AssetType;
+pub use crate::draft::Draft;
+pub use crate::draft::EpisodeDraft;
+pub use crate::draft::SeasonDraft;
+pub use crate::draft::SpecialDraft;
+pub use crate::draft::DESCRIPTION_LONG_MAX;
+pub use crate::draft::DESCRIPTION_SHORT_MAX;
+pub use crate::dry_run::PlannedRequest;
 pub use crate::error::MMCError;
 pub use crate::error::MMCResult;
+pub use crate::hierarchy::CrawledAsset;
+pub use crate::ingest::IngestStatus;
+pub use crate::interceptor::ApiRequest;
+pub use crate::interceptor::ApiResponse;
+pub use crate::interceptor::Interceptor;
+pub use crate::journal::FileJournal;
+pub use crate::journal::Journal;
+pub use crate::journal::JournalEntry;
+pub use crate::metrics::EndpointMetrics;
+pub use crate::metrics::Histogram;
+pub use crate::metrics::InMemoryMetrics;
+pub use crate::metrics::MetricKey;
+pub use crate::metrics::MetricsSink;
+pub use crate::metrics::MetricsSnapshot;
+pub use crate::metrics::RequestMetric;
+pub use crate::metrics::LATENCY_BUCKETS_MS;
+pub use crate::playability::playability;
+pub use crate::playability::Playability;
+pub use crate::playability::Unplayable;
+pub use crate::report::ExpiringEntry;
+pub use crate::report::ExpiringFailure;
+pub use crate::report::ExpiringIssue;
+pub use crate::report::ExpiringReport;
 
 #[cfg(test)]
+#[allow(clippy::redundant_static_lifetimes, clippy::useless_vec)]
 mod tests {
+    use chrono::{DateTime, Duration as TimeDelta, NaiveDate, Utc};
     use mockito::mock;
+    use mockito::Matcher;
     use mockito::Mock;
-    use reqwest::StatusCode;
+    use reqwest::{Method, StatusCode};
     use serde::Serialize;
     use uuid::Uuid;
 
+    use std::env;
+    use std::fs;
+    use std::io::{self, Write};
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use crate::asset::{Asset, GeoProfile};
+    use crate::audit::{AuditRecord, AuditSink, JsonLinesAudit};
+    use crate::availability::{availabilities_of, Audience, AvailabilityWindow};
+    use crate::batch::{BatchExecutor, BatchOperation, BatchOutcome, Operation};
     use crate::client::Client;
     use crate::client::Endpoints;
     use crate::client::Params;
+    use crate::credentials::{
+        ChainProvider, CredentialProvider, Credentials, EnvVarProvider, Environment, FileProvider,
+    };
+    use crate::diff::{AttributeDiff, ConflictPolicy};
+    use crate::draft::{
+        AssetDraft, AssetType, Draft, EpisodeDraft, SeasonDraft, SpecialDraft,
+        DESCRIPTION_SHORT_MAX,
+    };
+    use crate::dry_run::PlannedRequest;
     use crate::error::MMCError;
     use crate::error::MMCResult;
+    use crate::hierarchy::CrawledAsset;
+    use crate::ingest::IngestStatus;
+    use crate::interceptor::{ApiRequest, ApiResponse, Interceptor};
+    use crate::journal::{FileJournal, Journal};
+    use crate::metrics::{InMemoryMetrics, MetricKey};
+    use crate::playability::{playability, Playability, Unplayable};
+    use crate::report::{ExpiringIssue, ExpiringReport};
 
     const KEY: &'static str = "hello";
     const SECRET: &'static str = "world";
@@ -232,6 +622,26 @@ mod tests {
         m.assert();
     }
 
+    #[test]
+    fn list_encodes_params() {
+        let id = random_id();
+        let param_string = format!("?since=2020-01-01T00%3A00%3A00%2B01%3A00&type={}", id);
+
+        let m = mock_list("changelog", param_string.as_str())
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let resp = sample_client().changelog(vec![
+            ("since", "2020-01-01T00:00:00+01:00"),
+            ("type", id.as_str()),
+        ]);
+
+        assert_eq!(resp.unwrap(), "{}");
+
+        m.assert();
+    }
+
     #[test]
     fn basic_auth_ok() {
         let id = random_id();
@@ -468,6 +878,73 @@ mod tests {
         m.assert();
     }
 
+    #[test]
+    fn dry_run_update_is_not_sent() {
+        let id = random_id();
+
+        let m = mock_asset_update("assets", id.as_str()).expect(0).create();
+
+        let resp = sample_client().dry_run(true).update(
+            Endpoints::Asset,
+            id.as_str(),
+            &serde_json::json!({"data": {"attributes": {"title": "New"}}}),
+        );
+
+        let plan: PlannedRequest = serde_json::from_str(resp.unwrap().as_str()).unwrap();
+        let url = [
+            mockito::server_url(),
+            "/assets/".to_string(),
+            id,
+            "/edit/".to_string(),
+        ]
+        .join("");
+
+        assert_eq!(plan.method, "PATCH");
+        assert_eq!(plan.url, url);
+        assert_eq!(
+            plan.body,
+            Some(serde_json::json!({"data": {"attributes": {"title": "New"}}}))
+        );
+
+        m.assert();
+    }
+
+    #[test]
+    fn dry_run_still_sends_reads() {
+        let id = random_id();
+
+        let m = mock_edit("assets", id.as_str())
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let resp = sample_client()
+            .dry_run(true)
+            .edit(Endpoints::Asset, id.as_str());
+
+        assert_eq!(resp.unwrap(), "{}");
+
+        m.assert();
+    }
+
+    #[test]
+    fn dry_run_validates_moves() {
+        let resp = sample_client().dry_run(true).change_parent(
+            Endpoints::Franchise,
+            "franchise-id",
+            Endpoints::Episode,
+            "episode-id",
+        );
+
+        match resp.unwrap_err() {
+            MMCError::UnsupportedMoveParent(_) => (),
+            err => panic!(
+                "Expected UnsupportedMoveParent error but recieved {:?}",
+                err
+            ),
+        }
+    }
+
     #[test]
     fn delete_200() {
         let id = random_id();
@@ -512,4 +989,2028 @@ mod tests {
 
         m.assert();
     }
+
+    struct HeaderInterceptor;
+
+    impl Interceptor for HeaderInterceptor {
+        fn before_send(&self, request: &mut ApiRequest) -> Option<ApiResponse> {
+            request
+                .headers
+                .push(("X-Intercepted".to_string(), "yes".to_string()));
+            None
+        }
+    }
+
+    struct ShortCircuitInterceptor;
+
+    impl Interceptor for ShortCircuitInterceptor {
+        fn before_send(&self, request: &mut ApiRequest) -> Option<ApiResponse> {
+            Some(ApiResponse::new(
+                StatusCode::OK,
+                format!("{} {}", request.method, request.url).as_str(),
+            ))
+        }
+    }
+
+    struct RewriteInterceptor;
+
+    impl Interceptor for RewriteInterceptor {
+        fn after_response(&self, request: &ApiRequest, response: &mut ApiResponse) {
+            response.body = request.body.clone().unwrap_or_default();
+        }
+    }
+
+    #[test]
+    fn interceptor_adds_header() {
+        let id = random_id();
+        let m = mock_single("shows", id.as_str(), None)
+            .match_header("X-Intercepted", "yes")
+            .match_header("Authorization", BASIC_AUTH)
+            .with_status(200)
+            .with_body("{\"name\":\"value\"}")
+            .create();
+
+        let resp = sample_client().with_interceptor(HeaderInterceptor).get(
+            Endpoints::Show,
+            id.as_str(),
+            None,
+        );
+
+        assert_eq!(resp.unwrap(), "{\"name\":\"value\"}");
+
+        m.assert();
+    }
+
+    #[test]
+    fn interceptor_short_circuits() {
+        let id = random_id();
+        let m = mock_single("shows", id.as_str(), None).expect(0).create();
+
+        let resp = sample_client()
+            .with_interceptor(ShortCircuitInterceptor)
+            .with_interceptor(HeaderInterceptor)
+            .get(Endpoints::Show, id.as_str(), None);
+
+        assert!(resp.unwrap().starts_with("GET "));
+
+        m.assert();
+    }
+
+    #[test]
+    fn interceptor_sees_body_after_response() {
+        let id = random_id();
+        let m = mock_asset_update("assets", id.as_str())
+            .with_status(200)
+            .match_body("{}")
+            .create();
+
+        let resp = sample_client().with_interceptor(RewriteInterceptor).update(
+            Endpoints::Asset,
+            id.as_str(),
+            &EmptyReq {},
+        );
+
+        assert_eq!(resp.unwrap(), "{}");
+
+        m.assert();
+    }
+
+    #[test]
+    fn metrics_record_requests_and_errors() {
+        let found = random_id();
+        let missing = random_id();
+        let metrics = Arc::new(InMemoryMetrics::new());
+        let client = sample_client().with_metrics(metrics.clone());
+
+        let m1 = mock_single("seasons", found.as_str(), None)
+            .with_status(200)
+            .with_body("{}")
+            .create();
+        let m2 = mock_single("seasons", missing.as_str(), None)
+            .with_status(404)
+            .create();
+
+        let _ = client.season(found.as_str(), None);
+        let _ = client.season(missing.as_str(), None);
+
+        let snapshot = metrics.snapshot();
+        let entry = snapshot
+            .get(&MetricKey {
+                endpoint: Some(Endpoints::Season),
+                method: Method::GET,
+            })
+            .unwrap();
+
+        assert_eq!(entry.requests, 2);
+        assert_eq!(entry.errors.get("ResourceNotFound"), Some(&1));
+        assert_eq!(entry.latency.count, 2);
+        assert_eq!(entry.latency.buckets.iter().sum::<u64>(), 2);
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn metrics_record_retries() {
+        let id = random_id();
+        let m = mock_single("assets", id.as_str(), None)
+            .with_status(503)
+            .expect(3)
+            .create();
+
+        let metrics = Arc::new(InMemoryMetrics::new());
+        let client = sample_client()
+            .with_metrics(metrics.clone())
+            .with_retries(2);
+
+        match client.asset(id.as_str(), None) {
+            Err(MMCError::APIFailure(StatusCode::SERVICE_UNAVAILABLE)) => (),
+            err => panic!("Expected APIFailure error but recieved {:?}", err),
+        }
+
+        let snapshot = metrics.snapshot();
+        let entry = snapshot
+            .get(&MetricKey {
+                endpoint: Some(Endpoints::Asset),
+                method: Method::GET,
+            })
+            .unwrap();
+
+        assert_eq!(entry.retries, 2);
+        assert_eq!(entry.requests, 1);
+
+        m.assert();
+    }
+
+    // Collects audit records in memory so that tests can inspect them
+    #[derive(Default)]
+    struct MemoryAudit(Mutex<Vec<AuditRecord>>);
+
+    impl AuditSink for MemoryAudit {
+        fn record(&self, record: &AuditRecord) {
+            self.0.lock().unwrap().push(record.clone());
+        }
+    }
+
+    // A writer that can still be read after being handed to a JsonLinesAudit
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn audit_records_mutations() {
+        let parent = random_id();
+        let id = random_id();
+        let audit = Arc::new(MemoryAudit::default());
+        let client = sample_client().with_audit(audit.clone(), "editor@example.com");
+
+        let m1 = mock_create("shows", parent.as_str(), "assets")
+            .with_status(200)
+            .with_body(format!(r#"{{"data": {{"id": "{}"}}}}"#, id))
+            .create();
+        let m2 = mock_single("shows", parent.as_str(), None)
+            .with_status(200)
+            .with_body("{}")
+            .create();
+        let m3 = mock_delete("assets", id.as_str()).with_status(404).create();
+
+        let _ = client.create(
+            Endpoints::Show,
+            parent.as_str(),
+            Endpoints::Asset,
+            &serde_json::json!({"data": {"attributes": {"title": "New"}}}),
+        );
+        let _ = client.get(Endpoints::Show, parent.as_str(), None);
+        let _ = client.delete(Endpoints::Asset, id.as_str());
+
+        let records = audit.0.lock().unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].operator, "editor@example.com");
+        assert_eq!(records[0].method, "POST");
+        assert_eq!(records[0].status, Some(200));
+        assert_eq!(records[0].id, Some(id.clone()));
+        assert_eq!(
+            records[0].body,
+            Some(serde_json::json!({"data": {"attributes": {"title": "New"}}}))
+        );
+        assert_eq!(records[1].method, "DELETE");
+        assert_eq!(records[1].status, Some(404));
+        assert_eq!(records[1].id, Some(id.clone()));
+        assert_eq!(records[1].body, None);
+
+        m1.assert();
+        m2.assert();
+        m3.assert();
+    }
+
+    #[test]
+    fn audit_skips_dry_runs() {
+        let audit = Arc::new(MemoryAudit::default());
+        let client = sample_client()
+            .with_audit(audit.clone(), "editor@example.com")
+            .dry_run(true);
+
+        let _ = client.delete(Endpoints::Asset, random_id().as_str());
+
+        assert!(audit.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn audit_writes_json_lines() {
+        let id = random_id();
+        let buffer = SharedBuffer::default();
+        let client = sample_client().with_audit(
+            Arc::new(JsonLinesAudit::new(buffer.clone())),
+            "editor@example.com",
+        );
+
+        let m = mock_asset_update("assets", id.as_str())
+            .with_status(200)
+            .expect(2)
+            .create();
+
+        let _ = client.update(Endpoints::Asset, id.as_str(), &EmptyReq {});
+        let _ = client.update(Endpoints::Asset, id.as_str(), &EmptyReq {});
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["method"], "PATCH");
+        assert_eq!(lines[0]["operator"], "editor@example.com");
+        assert_eq!(lines[0]["status"], 200);
+        assert!(lines[0]["timestamp"].is_string());
+
+        m.assert();
+    }
+
+    fn temp_journal() -> (FileJournal, PathBuf) {
+        let path = env::temp_dir().join(format!("mm_journal_{}.jsonl", random_id()));
+        (FileJournal::new(&path), path)
+    }
+
+    #[test]
+    fn journal_captures_and_undoes_updates() {
+        let id = random_id();
+        let (journal, path) = temp_journal();
+        let journal = Arc::new(journal);
+        let client = sample_client().with_journal(journal.clone());
+
+        let previous = serde_json::json!({
+            "data": {
+                "type": "asset",
+                "id": id,
+                "attributes": {"title": "Old", "description_short": "Unchanged"}
+            }
+        });
+
+        let m1 = mock_edit("assets", id.as_str())
+            .with_status(200)
+            .with_body(previous.to_string())
+            .expect(2)
+            .create();
+        let m2 = mock_asset_update("assets", id.as_str())
+            .match_body(Matcher::Json(serde_json::json!({
+                "data": {"attributes": {"title": "New"}}
+            })))
+            .with_status(200)
+            .create();
+        let m3 = mock_asset_update("assets", id.as_str())
+            .match_body(Matcher::Json(serde_json::json!({
+                "data": {"type": "asset", "id": id, "attributes": {"title": "Old"}}
+            })))
+            .with_status(200)
+            .create();
+
+        let _ = client.update(
+            Endpoints::Asset,
+            id.as_str(),
+            &serde_json::json!({"data": {"attributes": {"title": "New"}}}),
+        );
+
+        let entries = journal.entries().unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].operation, Operation::Update);
+        assert_eq!(entries[0].object_id, id);
+        assert_eq!(entries[0].previous, previous);
+
+        let entry = journal.entry(entries[0].id.as_str()).unwrap();
+        let _ = client.undo(&entry);
+
+        m1.assert();
+        m2.assert();
+        m3.assert();
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn journal_undoes_deletes_beneath_parent() {
+        let id = random_id();
+        let parent = random_id();
+        let (journal, path) = temp_journal();
+        let journal = Arc::new(journal);
+        let client = sample_client().with_journal(journal.clone());
+
+        let m1 = mock_edit("assets", id.as_str())
+            .with_status(200)
+            .with_body(
+                serde_json::json!({
+                    "data": {
+                        "type": "asset",
+                        "id": id,
+                        "attributes": {
+                            "title": "Old",
+                            "episode": {"id": parent},
+                            "slug": "old",
+                            "created_at": "2020-01-01T00:00:00Z",
+                            "updated_at": "2020-01-02T00:00:00Z",
+                            "links": {}
+                        }
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+        let m2 = mock_delete("assets", id.as_str()).with_status(204).create();
+        let m3 = mock_create("episodes", parent.as_str(), "assets")
+            .match_body(Matcher::Json(serde_json::json!({
+                "data": {"type": "asset", "attributes": {"title": "Old"}}
+            })))
+            .with_status(200)
+            .create();
+
+        let _ = client.delete(Endpoints::Asset, id.as_str());
+
+        let entries = journal.entries().unwrap();
+
+        assert_eq!(entries[0].parent, Some(Endpoints::Episode));
+        assert_eq!(entries[0].parent_id, Some(parent.clone()));
+
+        let _ = client.undo(&entries[0]);
+
+        m1.assert();
+        m2.assert();
+        m3.assert();
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn journal_failure_prevents_delete() {
+        let id = random_id();
+        let (journal, _) = temp_journal();
+        let client = sample_client().with_journal(Arc::new(journal));
+
+        let m1 = mock_edit("assets", id.as_str()).with_status(404).create();
+        let m2 = mock_delete("assets", id.as_str()).expect(0).create();
+
+        match client.delete(Endpoints::Asset, id.as_str()).unwrap_err() {
+            MMCError::ResourceNotFound => (),
+            err => panic!("Expected ResourceNotFound error but recieved {:?}", err),
+        }
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn failed_delete_is_not_journaled() {
+        let id = random_id();
+        let (journal, path) = temp_journal();
+        let journal = Arc::new(journal);
+        let client = sample_client().with_journal(journal.clone());
+
+        let m1 = mock_edit("assets", id.as_str())
+            .with_status(200)
+            .with_body(
+                serde_json::json!({"data": {"type": "asset", "id": id, "attributes": {}}})
+                    .to_string(),
+            )
+            .create();
+        let m2 = mock_delete("assets", id.as_str()).with_status(403).create();
+
+        match client.delete(Endpoints::Asset, id.as_str()).unwrap_err() {
+            MMCError::NotAuthorized => (),
+            err => panic!("Expected NotAuthorized error but recieved {:?}", err),
+        }
+
+        assert!(journal.entries().unwrap().is_empty());
+
+        m1.assert();
+        m2.assert();
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn journal_reports_missing_entries() {
+        let (journal, _) = temp_journal();
+
+        match journal.entry("missing").unwrap_err() {
+            MMCError::Undo(_) => (),
+            err => panic!("Expected Undo error but recieved {:?}", err),
+        }
+    }
+
+    #[test]
+    fn debug_redacts_secret() {
+        let output = format!("{:?}", sample_client());
+
+        assert!(output.contains(KEY));
+        assert!(!output.contains(SECRET));
+        assert!(!format!("{:?}", Credentials::new(KEY, SECRET)).contains(SECRET));
+    }
+
+    #[test]
+    fn url_refuses_foreign_host() {
+        let resp = sample_client().url("https://example.com/shows/");
+
+        match resp.unwrap_err() {
+            MMCError::ForeignHost(url) => assert_eq!(url, "https://example.com/shows/"),
+            err => panic!("Expected ForeignHost error but recieved {:?}", err),
+        }
+    }
+
+    #[test]
+    fn url_allows_foreign_host_when_enabled() {
+        let id = random_id();
+        let m = mock_single("shows", id.as_str(), None)
+            .match_header("Authorization", BASIC_AUTH)
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let url = format!(
+            "http://localhost:{}/shows/{}/",
+            mockito::server_address().port(),
+            id
+        );
+
+        match sample_client().url(url.as_str()).unwrap_err() {
+            MMCError::ForeignHost(_) => (),
+            err => panic!("Expected ForeignHost error but recieved {:?}", err),
+        }
+
+        let resp = sample_client().allow_foreign_hosts(true).url(url.as_str());
+
+        assert_eq!(resp.unwrap(), "{}");
+
+        m.assert();
+    }
+
+    #[test]
+    fn env_provider_reads_per_environment() {
+        std::env::set_var("MM_ENV_TEST_STAGING_KEY", KEY);
+        std::env::set_var("MM_ENV_TEST_STAGING_SECRET", SECRET);
+
+        let provider = EnvVarProvider::with_prefix("MM_ENV_TEST");
+        let credentials = provider.credentials(Environment::Staging).unwrap();
+
+        assert_eq!(credentials.key(), KEY);
+        assert_eq!(credentials.secret(), SECRET);
+
+        match provider.credentials(Environment::Live).unwrap_err() {
+            MMCError::MissingCredentials(var) => assert_eq!(var, "MM_ENV_TEST_KEY"),
+            err => panic!("Expected MissingCredentials error but recieved {:?}", err),
+        }
+    }
+
+    #[test]
+    fn chain_provider_falls_back_to_file() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("mm_client_{}.json", random_id()));
+        std::fs::write(&path, "{\"live\":{\"key\":\"hello\",\"secret\":\"world\"}}").unwrap();
+
+        let provider = ChainProvider::new()
+            .with(EnvVarProvider::with_prefix("MM_CHAIN_TEST"))
+            .with(FileProvider::new(&path));
+
+        let credentials = provider.credentials(Environment::Live).unwrap();
+        assert_eq!(credentials.key(), KEY);
+        assert_eq!(credentials.secret(), SECRET);
+        assert!(provider.credentials(Environment::Staging).is_err());
+
+        let id = random_id();
+        let m = mock_single("shows", id.as_str(), None)
+            .match_header("Authorization", BASIC_AUTH)
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let client = Client::from_provider(&provider, Environment::Live).unwrap();
+        assert_eq!(client.show(id.as_str(), None).unwrap(), "{}");
+
+        std::fs::remove_file(&path).unwrap();
+        m.assert();
+    }
+
+    #[test]
+    fn file_provider_reads_toml() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("mm_client_{}.toml", random_id()));
+        std::fs::write(
+            &path,
+            "[staging]\nkey = \"hello\"\nsecret = \"world\"\n\n[profiles.other]\nkey = \"a\"\n",
+        )
+        .unwrap();
+
+        let credentials = FileProvider::new(&path)
+            .credentials(Environment::Staging)
+            .unwrap();
+        assert_eq!(credentials.key(), KEY);
+        assert_eq!(credentials.secret(), SECRET);
+
+        std::fs::write(&path, "[staging\nkey = ").unwrap();
+
+        let provider = ChainProvider::new()
+            .with(EnvVarProvider::with_prefix("MM_CHAIN_TEST"))
+            .with(FileProvider::new(&path));
+
+        match provider.credentials(Environment::Staging).unwrap_err() {
+            MMCError::InvalidCredentials(_) => (),
+            err => panic!("Expected InvalidCredentials error but recieved {:?}", err),
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn chain_provider_skips_missing_files() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("mm_client_{}.toml", random_id()));
+
+        let provider = ChainProvider::new()
+            .with(FileProvider::new(&path))
+            .with(EnvVarProvider::with_prefix("MM_CHAIN_MISSING_TEST"));
+
+        match provider.credentials(Environment::Live).unwrap_err() {
+            MMCError::MissingCredentials(sources) => {
+                assert!(sources.contains(path.to_str().unwrap()));
+                assert!(sources.contains("MM_CHAIN_MISSING_TEST_KEY"));
+            }
+            err => panic!("Expected MissingCredentials error but recieved {:?}", err),
+        }
+    }
+
+    #[test]
+    fn endpoints_round_trip() {
+        for endpoint in Endpoints::all() {
+            let parsed: Endpoints = endpoint.to_string().parse().unwrap();
+            assert_eq!(parsed, endpoint);
+        }
+    }
+
+    #[test]
+    fn custom_base_url() {
+        let id = random_id();
+        let m = mock_single("shows", id.as_str(), None)
+            .match_header("Authorization", BASIC_AUTH)
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let base = format!("{}/", mockito::server_url());
+        let client = Client::custom(KEY, SECRET, base.as_str()).unwrap();
+
+        assert_eq!(client.show(id.as_str(), None).unwrap(), "{}");
+
+        m.assert();
+    }
+
+    #[test]
+    fn list_all_follows_pages() {
+        let id = random_id();
+        let page_one = format!("?show_id={}&page=1", id);
+        let page_two = format!("?show_id={}&page=2", id);
+
+        let m1 = mock_list("seasons", page_one.as_str())
+            .with_status(200)
+            .with_body("{\"data\":[{\"id\":\"a\"}],\"links\":{\"next\":\"/seasons/?page=2\"}}")
+            .create();
+        let m2 = mock_list("seasons", page_two.as_str())
+            .with_status(200)
+            .with_body("{\"data\":[{\"id\":\"b\"}],\"links\":{\"next\":null}}")
+            .create();
+
+        let items = sample_client()
+            .list_all(
+                Endpoints::Season,
+                vec![("show_id", id.as_str()), ("page", "3")],
+            )
+            .unwrap();
+
+        let ids: Vec<&str> = items
+            .iter()
+            .filter_map(|item| item["id"].as_str())
+            .collect();
+        assert_eq!(ids, vec!["a", "b"]);
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn child_list_all_single_page() {
+        let id = random_id();
+        let m = mock_list(
+            ["shows/", id.as_str(), "/specials"].join("").as_str(),
+            "?page=1",
+        )
+        .with_status(200)
+        .with_body("{\"data\":[{\"id\":\"a\"},{\"id\":\"b\"}],\"links\":{}}")
+        .create();
+
+        let items = sample_client()
+            .child_list_all(Endpoints::Special, id.as_str(), Endpoints::Show, None)
+            .unwrap();
+
+        assert_eq!(items.len(), 2);
+
+        m.assert();
+    }
+
+    #[test]
+    fn changelog_all_follows_pages() {
+        let id = random_id();
+        let page_one = format!("?type={}&page=1", id);
+        let page_two = format!("?type={}&page=2", id);
+
+        let m1 = mock_list("changelog", page_one.as_str())
+            .with_status(200)
+            .with_body("{\"data\":[{\"id\":\"a\"}],\"links\":{\"next\":\"/changelog/?page=2\"}}")
+            .create();
+        let m2 = mock_list("changelog", page_two.as_str())
+            .with_status(200)
+            .with_body("{\"data\":[{\"id\":\"b\"}],\"links\":{\"next\":null}}")
+            .create();
+
+        let items = sample_client()
+            .changelog_all(vec![("type", id.as_str())])
+            .unwrap();
+
+        assert_eq!(items.len(), 2);
+
+        m1.assert();
+        m2.assert();
+    }
+
+    // Records the name of every span created while it is the default subscriber
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct SpanRecorder {
+        names: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for SpanRecorder {
+        fn enabled(&self, _: &tracing::Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes) -> tracing::span::Id {
+            let mut names = self.names.lock().unwrap();
+            names.push(span.metadata().name());
+            tracing::span::Id::from_u64(names.len() as u64)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record) {}
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, _: &tracing::Event) {}
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_records_spans_around_requests() {
+        let id = random_id();
+
+        let m = mock_list("changelog", format!("?type={}&page=1", id).as_str())
+            .with_status(200)
+            .with_body("{\"data\":[],\"links\":{\"next\":null}}")
+            .create();
+
+        let recorder = SpanRecorder::default();
+
+        tracing::subscriber::with_default(recorder.clone(), || {
+            sample_client()
+                .changelog_all(vec![("type", id.as_str())])
+                .unwrap();
+        });
+
+        let names = recorder.names.lock().unwrap();
+
+        assert_eq!(names.first(), Some(&"changelog_all"));
+        assert!(names.contains(&"changelog"));
+        assert!(names.contains(&"rq_send"));
+
+        m.assert();
+    }
+
+    #[test]
+    fn batch_continues_after_failure() {
+        let missing = random_id();
+        let updated = random_id();
+
+        let m1 = mock_delete("episodes", missing.as_str())
+            .with_status(404)
+            .create();
+        let m2 = mock_asset_update("episodes", updated.as_str())
+            .with_status(200)
+            .match_body("{\"title\":\"New\"}")
+            .with_body("{}")
+            .create();
+
+        let operations: Vec<BatchOperation> = [
+            format!(
+                "{{\"operation\":\"delete\",\"endpoint\":\"episodes\",\"id\":\"{}\"}}",
+                missing
+            ),
+            "{\"operation\":\"update\",\"endpoint\":\"episode\"}".to_string(),
+            format!(
+                "{{\"operation\":\"update\",\"endpoint\":\"episodes\",\"id\":\"{}\",\"body\":{{\"title\":\"New\"}}}}",
+                updated
+            ),
+        ]
+        .iter()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+        let client = sample_client();
+        let outcomes = BatchExecutor::new(&client)
+            .with_concurrency(2)
+            .run(&operations);
+
+        assert_eq!(operations[1].operation, Operation::Update);
+
+        match outcomes[0] {
+            BatchOutcome::Failed(MMCError::ResourceNotFound) => (),
+            ref outcome => panic!("Expected ResourceNotFound error but recieved {:?}", outcome),
+        }
+
+        match outcomes[1] {
+            BatchOutcome::Failed(MMCError::InvalidOperation(_)) => (),
+            ref outcome => panic!("Expected InvalidOperation error but recieved {:?}", outcome),
+        }
+
+        assert!(outcomes[2].is_success());
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn batch_skips_after_failure_when_stopping() {
+        let operations = vec![
+            BatchOperation {
+                operation: Operation::Delete,
+                endpoint: Endpoints::Episode,
+                id: None,
+                parent: None,
+                parent_id: None,
+                body: None,
+            };
+            2
+        ];
+
+        let client = sample_client();
+        let outcomes = BatchExecutor::new(&client)
+            .with_concurrency(1)
+            .continue_on_error(false)
+            .run(&operations);
+
+        match outcomes[1] {
+            BatchOutcome::Skipped => (),
+            ref outcome => panic!("Expected Skipped outcome but recieved {:?}", outcome),
+        }
+    }
+
+    #[test]
+    fn attribute_diff_only_changed() {
+        let current = serde_json::json!({
+            "data": {
+                "type": "episode",
+                "id": "abc",
+                "attributes": {"title": "Old", "slug": "same", "ordinal": 1}
+            }
+        });
+        let desired = serde_json::json!({
+            "data": {
+                "type": "episode",
+                "id": "abc",
+                "attributes": {"title": "New", "slug": "same", "ordinal": 1}
+            }
+        });
+
+        let diff = AttributeDiff::between(&current, &desired).unwrap();
+        assert_eq!(diff.names(), vec!["title"]);
+        assert_eq!(
+            diff.patch_body("episode", "abc"),
+            serde_json::json!({
+                "data": {"type": "episode", "id": "abc", "attributes": {"title": "New"}}
+            })
+        );
+
+        assert!(AttributeDiff::between(&current, &current)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn attribute_diff_bare_attributes() {
+        let current = serde_json::json!({"title": "Old", "ordinal": 1});
+        let desired = serde_json::json!({"attributes": {"ordinal": 2}});
+
+        let diff = AttributeDiff::between(&current, &desired).unwrap();
+        assert_eq!(diff.changed.get("ordinal"), Some(&serde_json::json!(2)));
+        assert_eq!(diff.changed.len(), 1);
+    }
+
+    #[test]
+    fn attribute_diff_refuses_documents_without_attributes() {
+        let current = serde_json::json!({"data": {"type": "episode", "id": "abc"}});
+        let desired = serde_json::json!({"title": "New"});
+
+        match AttributeDiff::between(&current, &desired) {
+            Err(MMCError::InvalidDocument(_)) => (),
+            err => panic!("Expected InvalidDocument error but recieved {:?}", err),
+        }
+
+        match AttributeDiff::between(&desired, &current) {
+            Err(MMCError::InvalidDocument(_)) => (),
+            err => panic!("Expected InvalidDocument error but recieved {:?}", err),
+        }
+    }
+
+    #[test]
+    fn attribute_diff_conflicts() {
+        let baseline = serde_json::json!({"title": "Original", "slug": "a", "ordinal": 1});
+        let current = serde_json::json!({"title": "Theirs", "slug": "b", "ordinal": 1});
+        let desired = serde_json::json!({"title": "Mine", "slug": "b", "ordinal": 2});
+
+        let diff = AttributeDiff::between(&current, &desired).unwrap();
+
+        // The slug was changed by someone else, but is not being changed here
+        assert_eq!(diff.conflicts(&baseline, &current).unwrap(), vec!["title"]);
+    }
+
+    fn mock_changed_edit(id: &str) -> Mock {
+        mock_edit("assets", id)
+            .with_status(200)
+            .with_body(
+                serde_json::json!({
+                    "data": {
+                        "type": "asset",
+                        "id": id,
+                        "attributes": {"title": "Theirs", "slug": "same"}
+                    }
+                })
+                .to_string(),
+            )
+            .create()
+    }
+
+    #[test]
+    fn update_changed_sends_only_changed_attributes() {
+        let id = random_id();
+
+        let m1 = mock_changed_edit(id.as_str());
+        let m2 = mock_asset_update("assets", id.as_str())
+            .match_body(Matcher::Json(serde_json::json!({
+                "data": {"type": "asset", "id": id, "attributes": {"title": "Mine"}}
+            })))
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let result = sample_client()
+            .update_changed(
+                Endpoints::Asset,
+                id.as_str(),
+                &serde_json::json!({"title": "Mine", "slug": "same"}),
+            )
+            .unwrap();
+
+        assert_eq!(result.diff.names(), vec!["title"]);
+        assert_eq!(result.response, Some("{}".to_string()));
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn update_changed_skips_unchanged_objects() {
+        let id = random_id();
+
+        let m1 = mock_changed_edit(id.as_str());
+        let m2 = mock_asset_update("assets", id.as_str()).expect(0).create();
+
+        let result = sample_client()
+            .update_changed(
+                Endpoints::Asset,
+                id.as_str(),
+                &serde_json::json!({"title": "Theirs"}),
+            )
+            .unwrap();
+
+        assert!(result.diff.is_empty());
+        assert_eq!(result.response, None);
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn update_changed_since_refuses_conflicts() {
+        let id = random_id();
+
+        let m1 = mock_changed_edit(id.as_str());
+        let m2 = mock_asset_update("assets", id.as_str()).expect(0).create();
+
+        let resp = sample_client().update_changed_since(
+            Endpoints::Asset,
+            id.as_str(),
+            &serde_json::json!({"title": "Mine"}),
+            &serde_json::json!({"title": "Original", "slug": "same"}),
+            ConflictPolicy::Refuse,
+        );
+
+        match resp.unwrap_err() {
+            MMCError::Conflict(names) => assert_eq!(names, vec!["title"]),
+            err => panic!("Expected Conflict error but recieved {:?}", err),
+        }
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn update_changed_since_warns_of_conflicts() {
+        let id = random_id();
+
+        let m1 = mock_changed_edit(id.as_str());
+        let m2 = mock_asset_update("assets", id.as_str())
+            .with_status(200)
+            .create();
+
+        let result = sample_client()
+            .update_changed_since(
+                Endpoints::Asset,
+                id.as_str(),
+                &serde_json::json!({"title": "Mine"}),
+                &serde_json::json!({"title": "Original", "slug": "same"}),
+                ConflictPolicy::Warn,
+            )
+            .unwrap();
+
+        assert_eq!(result.conflicts, vec!["title"]);
+        assert!(result.response.is_some());
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn drafts_serialize_to_documents() {
+        let episode = EpisodeDraft::new("Pilot", 1)
+            .with_description_short("Short")
+            .with_premiered_on(NaiveDate::from_ymd_opt(2020, 1, 2).unwrap());
+
+        assert_eq!(
+            serde_json::to_value(&episode).unwrap(),
+            serde_json::json!({
+                "data": {
+                    "type": "episode",
+                    "attributes": {
+                        "title": "Pilot",
+                        "ordinal": 1,
+                        "description_short": "Short",
+                        "premiered_on": "2020-01-02"
+                    }
+                }
+            })
+        );
+
+        let season = SeasonDraft::edit("season-id").with_title("Season");
+
+        assert_eq!(
+            serde_json::to_value(&season).unwrap(),
+            serde_json::json!({
+                "data": {
+                    "type": "season",
+                    "id": "season-id",
+                    "attributes": {"title": "Season"}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn drafts_validate_required_attributes() {
+        match EpisodeDraft::new("Pilot", 1)
+            .with_description_short("Short")
+            .validate()
+        {
+            Err(MMCError::InvalidDraft(reason)) => assert!(reason.contains("description_long")),
+            other => panic!("Expected InvalidDraft error but recieved {:?}", other),
+        }
+
+        assert!(SeasonDraft::new(1).validate().is_ok());
+        assert!(SpecialDraft::edit("special-id").validate().is_ok());
+    }
+
+    #[test]
+    fn drafts_validate_limits() {
+        let long = "x".repeat(DESCRIPTION_SHORT_MAX + 1);
+
+        match SpecialDraft::edit("special-id")
+            .with_description_short(long.as_str())
+            .validate()
+        {
+            Err(MMCError::InvalidDraft(reason)) => assert!(reason.contains("description_short")),
+            other => panic!("Expected InvalidDraft error but recieved {:?}", other),
+        }
+
+        match SeasonDraft::new(0).validate() {
+            Err(MMCError::InvalidDraft(reason)) => assert!(reason.contains("ordinal")),
+            other => panic!("Expected InvalidDraft error but recieved {:?}", other),
+        }
+
+        let exact = "x".repeat(DESCRIPTION_SHORT_MAX);
+        assert!(SpecialDraft::edit("special-id")
+            .with_description_short(exact.as_str())
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn create_draft_posts_beneath_parent() {
+        let id = random_id();
+        let draft = SpecialDraft::new("Special")
+            .with_description_short("Short")
+            .with_description_long("Long");
+
+        let m = mock_create("shows", id.as_str(), "specials")
+            .match_body(mockito::Matcher::Json(
+                serde_json::to_value(&draft).unwrap(),
+            ))
+            .with_status(200)
+            .with_body("{\"data\":{\"id\":\"new-special\"}}")
+            .create();
+
+        let client = sample_client();
+        let response = client.create_draft(id.as_str(), &draft).unwrap();
+
+        assert!(response.contains("new-special"));
+        m.assert();
+    }
+
+    #[test]
+    fn invalid_drafts_are_not_sent() {
+        let id = random_id();
+        let m = mock_create("seasons", id.as_str(), "episodes")
+            .expect(0)
+            .create();
+
+        let client = sample_client();
+
+        match client.create_draft(id.as_str(), &EpisodeDraft::new("Pilot", 1)) {
+            Err(MMCError::InvalidDraft(_)) => (),
+            other => panic!("Expected InvalidDraft error but recieved {:?}", other),
+        }
+
+        match client.create_draft(id.as_str(), &EpisodeDraft::edit("episode-id")) {
+            Err(MMCError::InvalidDraft(_)) => (),
+            other => panic!("Expected InvalidDraft error but recieved {:?}", other),
+        }
+
+        match client.update_draft(&SeasonDraft::new(1)) {
+            Err(MMCError::InvalidDraft(_)) => (),
+            other => panic!("Expected InvalidDraft error but recieved {:?}", other),
+        }
+
+        m.assert();
+    }
+
+    #[test]
+    fn update_draft_patches_object() {
+        let id = random_id();
+        let draft = EpisodeDraft::edit(id.as_str()).with_title("New title");
+
+        let m = mock_asset_update("episodes", id.as_str())
+            .match_body(mockito::Matcher::Json(
+                serde_json::to_value(&draft).unwrap(),
+            ))
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let client = sample_client();
+        client.update_draft(&draft).unwrap();
+
+        m.assert();
+    }
+
+    fn sample_asset() -> AssetDraft {
+        AssetDraft::new("Pilot", AssetType::Clip)
+            .with_video("hd-1080p-mezzanine-16x9", "https://example.org/pilot.mp4")
+            .with_caption("https://example.org/pilot.srt", "en")
+            .with_image("asset-mezzanine-16x9", "https://example.org/pilot.jpg")
+            .with_tag("pilot")
+    }
+
+    fn ingest_body(video: &str, image: &str) -> String {
+        serde_json::json!({
+            "data": {
+                "attributes": {
+                    "video": {"ingestion_status": video},
+                    "images": [{"ingestion_status": image, "ingestion_error": "Unreadable"}]
+                }
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn asset_drafts_serialize_to_documents() {
+        let start: DateTime<Utc> = "2020-01-01T00:00:00Z".parse().unwrap();
+        let asset = sample_asset().with_availability(
+            Audience::AllMembers,
+            AvailabilityWindow::new(Some(start), None),
+        );
+
+        assert_eq!(
+            serde_json::to_value(&asset).unwrap(),
+            serde_json::json!({
+                "data": {
+                    "type": "asset",
+                    "attributes": {
+                        "title": "Pilot",
+                        "object_type": "clip",
+                        "tags": ["pilot"],
+                        "video": {
+                            "profile": "hd-1080p-mezzanine-16x9",
+                            "source": "https://example.org/pilot.mp4"
+                        },
+                        "caption": {"source": "https://example.org/pilot.srt", "language": "en"},
+                        "images": [{
+                            "profile": "asset-mezzanine-16x9",
+                            "source": "https://example.org/pilot.jpg"
+                        }],
+                        "availabilities": {
+                            "all_members": {"start": "2020-01-01T00:00:00Z", "end": null}
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn asset_drafts_validate_sources_and_windows() {
+        assert!(sample_asset().validate().is_ok());
+
+        match sample_asset()
+            .with_image("asset-mezzanine-16x9", "ftp://example.org/pilot.jpg")
+            .validate()
+        {
+            Err(MMCError::InvalidDraft(reason)) => assert!(reason.contains("image")),
+            other => panic!("Expected InvalidDraft error but recieved {:?}", other),
+        }
+
+        let start: DateTime<Utc> = "2020-01-01T00:00:00Z".parse().unwrap();
+
+        match sample_asset()
+            .with_availability(
+                Audience::Public,
+                AvailabilityWindow::new(Some(start), Some(start)),
+            )
+            .validate()
+        {
+            Err(MMCError::InvalidWindow(reason)) => assert!(reason.contains("public")),
+            other => panic!("Expected InvalidWindow error but recieved {:?}", other),
+        }
+    }
+
+    #[test]
+    fn create_asset_posts_beneath_parent() {
+        let id = random_id();
+        let asset = sample_asset();
+
+        let m = mock_create("episodes", id.as_str(), "assets")
+            .match_body(Matcher::Json(serde_json::to_value(&asset).unwrap()))
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let client = sample_client();
+        client
+            .create_asset(Endpoints::Episode, id.as_str(), &asset)
+            .unwrap();
+
+        m.assert();
+
+        match client.create_asset(Endpoints::Collection, id.as_str(), &asset) {
+            Err(MMCError::InvalidDraft(_)) => (),
+            other => panic!("Expected InvalidDraft error but recieved {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ingest_status_of_assets() {
+        let status = |video, image| {
+            IngestStatus::of(&serde_json::from_str(ingest_body(video, image).as_str()).unwrap())
+        };
+
+        assert_eq!(status("done", "done"), IngestStatus::Done);
+        assert_eq!(status("in_progress", "done"), IngestStatus::InProgress);
+
+        let unstarted = serde_json::json!({"data": {"attributes": {"video": {}}}});
+        assert_eq!(IngestStatus::of(&unstarted), IngestStatus::InProgress);
+
+        match status("in_progress", "failed") {
+            IngestStatus::Failed(reason) => assert!(reason.contains("Unreadable")),
+            other => panic!("Expected Failed status but recieved {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ingest_status_of_new_assets() {
+        let created = serde_json::json!({
+            "data": {"attributes": {"title": "Pilot", "video": null, "images": []}}
+        });
+
+        assert_eq!(IngestStatus::of(&created), IngestStatus::InProgress);
+        assert_eq!(
+            IngestStatus::of_draft(&created, &sample_asset()),
+            IngestStatus::InProgress
+        );
+
+        let partial: serde_json::Value =
+            serde_json::from_str(ingest_body("done", "done").as_str()).unwrap();
+
+        assert_eq!(IngestStatus::of(&partial), IngestStatus::Done);
+        assert_eq!(
+            IngestStatus::of_draft(&partial, &sample_asset()),
+            IngestStatus::InProgress
+        );
+
+        let nothing = AssetDraft::new("Pilot", AssetType::Clip);
+        assert_eq!(
+            IngestStatus::of_draft(&created, &nothing),
+            IngestStatus::Done
+        );
+    }
+
+    #[test]
+    fn wait_for_ingest_polls_until_done() {
+        let id = random_id();
+
+        let m1 = mock_edit("assets", id.as_str())
+            .with_status(200)
+            .with_body(ingest_body("in_progress", "done"))
+            .expect(1)
+            .create();
+
+        let client = sample_client();
+
+        match client.wait_for_ingest(
+            id.as_str(),
+            Duration::from_millis(1),
+            Duration::from_secs(0),
+        ) {
+            Err(MMCError::Ingest(reason)) => assert!(reason.contains("did not finish")),
+            other => panic!("Expected Ingest error but recieved {:?}", other),
+        }
+
+        m1.assert();
+        drop(m1);
+
+        let m2 = mock_edit("assets", id.as_str())
+            .with_status(200)
+            .with_body(ingest_body("done", "done"))
+            .create();
+
+        let body = client
+            .wait_for_ingest(
+                id.as_str(),
+                Duration::from_millis(1),
+                Duration::from_secs(1),
+            )
+            .unwrap();
+
+        assert_eq!(body, ingest_body("done", "done"));
+        m2.assert();
+    }
+
+    #[test]
+    fn wait_for_ingest_reports_failures() {
+        let id = random_id();
+
+        let m = mock_edit("assets", id.as_str())
+            .with_status(200)
+            .with_body(ingest_body("done", "failed"))
+            .create();
+
+        let client = sample_client();
+
+        match client.wait_for_ingest(
+            id.as_str(),
+            Duration::from_millis(1),
+            Duration::from_secs(1),
+        ) {
+            Err(MMCError::Ingest(reason)) => assert!(reason.contains("image")),
+            other => panic!("Expected Ingest error but recieved {:?}", other),
+        }
+
+        m.assert();
+    }
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn mock_children(parent: &str, id: &str, child: &str, items: serde_json::Value) -> Mock {
+        mock_list([parent, "/", id, "/", child].join("").as_str(), "?page=1")
+            .with_status(200)
+            .with_body(serde_json::json!({"data": items, "links": {}}).to_string())
+            .create()
+    }
+
+    fn windows_body() -> String {
+        serde_json::json!({
+            "data": {
+                "attributes": {
+                    "availabilities": {
+                        "public": {"start": "2020-01-01T00:00:00Z", "end": "2020-02-01T00:00:00Z"},
+                        "all_members": {"start": "2020-01-01T00:00:00Z", "end": null},
+                        "station_members": null
+                    }
+                }
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn availability_window_changes() {
+        let window = AvailabilityWindow::new(
+            Some(instant("2020-01-01T00:00:00Z")),
+            Some(instant("2020-02-01T00:00:00Z")),
+        );
+
+        assert_eq!(
+            window
+                .shifted(Audience::Public, TimeDelta::days(1))
+                .unwrap(),
+            AvailabilityWindow::new(
+                Some(instant("2020-01-02T00:00:00Z")),
+                Some(instant("2020-02-02T00:00:00Z")),
+            )
+        );
+        assert_eq!(
+            window
+                .extended(Audience::Public, TimeDelta::days(1))
+                .unwrap()
+                .end,
+            Some(instant("2020-02-02T00:00:00Z"))
+        );
+        assert!(window.contains(instant("2020-01-01T00:00:00Z")));
+        assert!(!window.contains(instant("2020-02-01T00:00:00Z")));
+
+        match AvailabilityWindow::default().extended(Audience::Public, TimeDelta::days(1)) {
+            Err(MMCError::InvalidWindow(_)) => (),
+            other => panic!("Expected InvalidWindow error but recieved {:?}", other),
+        }
+
+        match window.extended(Audience::Public, TimeDelta::milliseconds(i64::MAX)) {
+            Err(MMCError::InvalidWindow(_)) => (),
+            other => panic!("Expected InvalidWindow error but recieved {:?}", other),
+        }
+
+        match window.shifted(Audience::Public, TimeDelta::milliseconds(-i64::MAX)) {
+            Err(MMCError::InvalidWindow(_)) => (),
+            other => panic!("Expected InvalidWindow error but recieved {:?}", other),
+        }
+
+        match window
+            .expired(instant("2019-12-01T00:00:00Z"))
+            .validate(Audience::Public)
+        {
+            Err(MMCError::InvalidWindow(_)) => (),
+            other => panic!("Expected InvalidWindow error but recieved {:?}", other),
+        }
+    }
+
+    #[test]
+    fn availabilities_of_skips_null_windows() {
+        let windows =
+            availabilities_of(&serde_json::from_str(windows_body().as_str()).unwrap()).unwrap();
+
+        assert_eq!(
+            windows.keys().collect::<Vec<_>>(),
+            vec![&Audience::Public, &Audience::AllMembers]
+        );
+        assert_eq!(windows[&Audience::AllMembers].end, None);
+    }
+
+    #[test]
+    fn set_availability_keeps_other_windows() {
+        let id = random_id();
+
+        let m1 = mock_edit("assets", id.as_str())
+            .with_status(200)
+            .with_body(windows_body())
+            .create();
+
+        let m2 = mock_asset_update("assets", id.as_str())
+            .match_body(Matcher::Json(serde_json::json!({
+                "data": {
+                    "type": "asset",
+                    "id": id,
+                    "attributes": {
+                        "availabilities": {
+                            "public": {"start": "2020-01-01T00:00:00Z", "end": "2020-02-01T00:00:00Z"},
+                            "all_members": {"start": "2020-01-01T00:00:00Z", "end": null},
+                            "station_members": {"start": "2020-03-01T00:00:00Z", "end": null}
+                        }
+                    }
+                }
+            })))
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let client = sample_client();
+        client
+            .set_availability(
+                id.as_str(),
+                Audience::StationMembers,
+                AvailabilityWindow::new(Some(instant("2020-03-01T00:00:00Z")), None),
+            )
+            .unwrap();
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn set_availability_ignores_unchanged_invalid_windows() {
+        let id = random_id();
+        let invalid =
+            serde_json::json!({"start": "2020-02-01T00:00:00Z", "end": "2020-01-01T00:00:00Z"});
+
+        let m1 = mock_edit("assets", id.as_str())
+            .with_status(200)
+            .with_body(
+                serde_json::json!({
+                    "data": {"attributes": {"availabilities": {"public": invalid}}}
+                })
+                .to_string(),
+            )
+            .create();
+
+        let m2 = mock_asset_update("assets", id.as_str())
+            .match_body(Matcher::Json(serde_json::json!({
+                "data": {
+                    "type": "asset",
+                    "id": id,
+                    "attributes": {
+                        "availabilities": {
+                            "public": invalid,
+                            "station_members": {"start": "2020-03-01T00:00:00Z", "end": null}
+                        }
+                    }
+                }
+            })))
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let client = sample_client();
+        client
+            .set_availability(
+                id.as_str(),
+                Audience::StationMembers,
+                AvailabilityWindow::new(Some(instant("2020-03-01T00:00:00Z")), None),
+            )
+            .unwrap();
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn invalid_availability_changes_are_not_sent() {
+        let id = random_id();
+
+        let m1 = mock_edit("assets", id.as_str())
+            .with_status(200)
+            .with_body(windows_body())
+            .expect(3)
+            .create();
+
+        let m2 = mock_asset_update("assets", id.as_str()).expect(0).create();
+
+        let client = sample_client();
+
+        match client.extend_availability(id.as_str(), Audience::AllMembers, TimeDelta::days(1)) {
+            Err(MMCError::InvalidWindow(_)) => (),
+            other => panic!("Expected InvalidWindow error but recieved {:?}", other),
+        }
+
+        match client.expire_availability(
+            id.as_str(),
+            Audience::StationMembers,
+            instant("2020-03-01T00:00:00Z"),
+        ) {
+            Err(MMCError::InvalidWindow(_)) => (),
+            other => panic!("Expected InvalidWindow error but recieved {:?}", other),
+        }
+
+        match client.expire_availability(
+            id.as_str(),
+            Audience::Public,
+            instant("2019-12-01T00:00:00Z"),
+        ) {
+            Err(MMCError::InvalidWindow(_)) => (),
+            other => panic!("Expected InvalidWindow error but recieved {:?}", other),
+        }
+
+        m1.assert();
+        m2.assert();
+    }
+
+    #[test]
+    fn crawl_assets_records_ancestors() {
+        let show = random_id();
+        let season = random_id();
+        let episode = random_id();
+
+        let mocks = vec![
+            mock_children("shows", show.as_str(), "assets", serde_json::json!([])),
+            mock_children(
+                "shows",
+                show.as_str(),
+                "seasons",
+                serde_json::json!([{"id": season}]),
+            ),
+            mock_children("shows", show.as_str(), "specials", serde_json::json!([])),
+            mock_children("seasons", season.as_str(), "assets", serde_json::json!([])),
+            mock_children(
+                "seasons",
+                season.as_str(),
+                "episodes",
+                serde_json::json!([{"id": episode}]),
+            ),
+            mock_children(
+                "episodes",
+                episode.as_str(),
+                "assets",
+                serde_json::json!([{"id": "asset"}]),
+            ),
+        ];
+
+        let assets = sample_client()
+            .crawl_assets(Endpoints::Show, show.as_str())
+            .unwrap();
+
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].asset, serde_json::json!({"id": "asset"}));
+        assert_eq!(
+            assets[0].ancestor(&Endpoints::Season),
+            Some(&serde_json::json!({"id": season}))
+        );
+        assert_eq!(assets[0].ancestors.len(), 2);
+
+        for m in mocks {
+            m.assert();
+        }
+    }
+
+    #[test]
+    fn shift_availability_beneath_season() {
+        let season = random_id();
+        let episode = random_id();
+        let moved = random_id();
+        let missing = random_id();
+
+        let m1 = mock_children(
+            "seasons",
+            season.as_str(),
+            "assets",
+            serde_json::json!([{"id": moved}]),
+        );
+        let m2 = mock_children(
+            "seasons",
+            season.as_str(),
+            "episodes",
+            serde_json::json!([{"id": episode}]),
+        );
+        let m3 = mock_children(
+            "episodes",
+            episode.as_str(),
+            "assets",
+            serde_json::json!([{"id": missing}]),
+        );
+
+        let m4 = mock_edit("assets", moved.as_str())
+            .with_status(200)
+            .with_body(windows_body())
+            .create();
+
+        let m5 = mock_asset_update("assets", moved.as_str())
+            .match_body(Matcher::Json(serde_json::json!({
+                "data": {
+                    "type": "asset",
+                    "id": moved,
+                    "attributes": {
+                        "availabilities": {
+                            "public": {"start": "2020-01-08T00:00:00Z", "end": "2020-02-08T00:00:00Z"},
+                            "all_members": {"start": "2020-01-08T00:00:00Z", "end": null}
+                        }
+                    }
+                }
+            })))
+            .with_status(200)
+            .with_body("{}")
+            .create();
+
+        let m6 = mock_edit("assets", missing.as_str())
+            .with_status(404)
+            .create();
+
+        let results = sample_client()
+            .shift_availability_beneath(Endpoints::Season, season.as_str(), TimeDelta::weeks(1))
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, moved);
+        assert!(results[0].1.is_ok());
+
+        match results[1].1 {
+            Err(MMCError::ResourceNotFound) => (),
+            ref result => panic!("Expected ResourceNotFound error but recieved {:?}", result),
+        }
+
+        for m in [m1, m2, m3, m4, m5, m6].iter() {
+            m.assert();
+        }
+    }
+
+    fn playable_asset() -> Asset {
+        Asset::from_value(&serde_json::json!({
+            "data": {
+                "id": "asset",
+                "attributes": {
+                    "title": "Pilot",
+                    "publish_state": 1,
+                    "geo_profile": {"name": "Domestic", "countries": ["US", "PR"]},
+                    "availabilities": {
+                        "public": {"start": "2020-01-01T00:00:00Z", "end": "2020-02-01T00:00:00Z"},
+                        "all_members": {"start": "2020-01-01T00:00:00Z", "end": "2020-06-01T00:00:00Z"},
+                        "station_members": {"start": "2019-12-01T00:00:00Z", "end": null}
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn asset_from_value() {
+        let asset = playable_asset();
+
+        assert_eq!(asset.id, "asset");
+        assert_eq!(asset.title, Some("Pilot".to_string()));
+        assert!(asset.published);
+        assert!(asset.geo_profile.unwrap().allows("pr"));
+        assert_eq!(asset.availabilities.len(), 3);
+
+        let listed =
+            Asset::from_value(&serde_json::json!({"id": "listed", "attributes": {}})).unwrap();
+
+        assert_eq!(listed.id, "listed");
+        assert!(!listed.published);
+        assert!(listed.availabilities.is_empty());
+    }
+
+    #[test]
+    fn playability_by_audience() {
+        let asset = playable_asset();
+        let at = instant("2020-03-01T00:00:00Z");
+
+        assert_eq!(
+            playability(&asset, Audience::Public, "US", at),
+            Playability::Unplayable(Unplayable::Expired(instant("2020-02-01T00:00:00Z")))
+        );
+        assert!(playability(&asset, Audience::AllMembers, "US", at).is_playable());
+        assert!(playability(&asset, Audience::StationMembers, "US", at).is_playable());
+
+        assert_eq!(
+            playability(
+                &asset,
+                Audience::Public,
+                "US",
+                instant("2019-12-15T00:00:00Z")
+            ),
+            Playability::Unplayable(Unplayable::NotYetAvailable(instant("2020-01-01T00:00:00Z")))
+        );
+    }
+
+    #[test]
+    fn playability_reasons() {
+        let mut asset = playable_asset();
+        let at = instant("2020-01-15T00:00:00Z");
+
+        assert_eq!(
+            playability(&asset, Audience::Public, "CA", at),
+            Playability::Unplayable(Unplayable::GeoRestricted("CA".to_string()))
+        );
+
+        asset.geo_profile = None;
+        assert!(playability(&asset, Audience::Public, "CA", at).is_playable());
+
+        asset.geo_profile = Some(GeoProfile::default());
+        assert!(playability(&asset, Audience::Public, "CA", at).is_playable());
+
+        asset.availabilities.remove(&Audience::Public);
+        assert_eq!(
+            playability(&asset, Audience::Public, "US", at),
+            Playability::Unplayable(Unplayable::NoWindow(Audience::Public))
+        );
+
+        asset.published = false;
+        assert_eq!(
+            playability(&asset, Audience::StationMembers, "US", at),
+            Playability::Unplayable(Unplayable::Unpublished)
+        );
+    }
+
+    fn report_asset(
+        id: &str,
+        public_end: Option<&str>,
+        member_end: Option<&str>,
+    ) -> serde_json::Value {
+        let mut availabilities = serde_json::json!({
+            "all_members": {"start": "2020-01-01T00:00:00Z", "end": member_end}
+        });
+
+        if let Some(end) = public_end {
+            availabilities["public"] =
+                serde_json::json!({"start": "2020-01-01T00:00:00Z", "end": end});
+        }
+
+        serde_json::json!({
+            "id": id,
+            "attributes": {"title": id, "availabilities": availabilities}
+        })
+    }
+
+    #[test]
+    fn expiring_report_from_crawl() {
+        let show = serde_json::json!({"id": "show", "attributes": {"title": "Show"}});
+        let season = |id: &str, ordinal: u64| {
+            (
+                Endpoints::Season,
+                serde_json::json!({"id": id, "attributes": {"ordinal": ordinal}}),
+            )
+        };
+        let special = (Endpoints::Special, serde_json::json!({"id": "special"}));
+
+        let assets = vec![
+            CrawledAsset {
+                ancestors: vec![special],
+                asset: report_asset("special-asset", None, None),
+            },
+            CrawledAsset {
+                ancestors: vec![season("two", 2)],
+                asset: report_asset(
+                    "late",
+                    Some("2020-03-10T00:00:00Z"),
+                    Some("2020-03-20T00:00:00Z"),
+                ),
+            },
+            CrawledAsset {
+                ancestors: vec![season("one", 1)],
+                asset: report_asset("kept", Some("2020-06-01T00:00:00Z"), None),
+            },
+            CrawledAsset {
+                ancestors: vec![season("two", 2)],
+                asset: report_asset("early", Some("2020-03-05T00:00:00Z"), None),
+            },
+        ];
+
+        let report =
+            ExpiringReport::from_crawl(&show, &assets, instant("2020-03-01T00:00:00Z"), 14)
+                .unwrap();
+
+        let listed: Vec<(&str, ExpiringIssue, Option<Audience>)> = report
+            .entries
+            .iter()
+            .map(|entry| (entry.asset_id.as_str(), entry.issue, entry.audience))
+            .collect();
+
+        assert_eq!(
+            listed,
+            vec![
+                ("early", ExpiringIssue::Expiring, Some(Audience::Public)),
+                ("late", ExpiringIssue::Expiring, Some(Audience::Public)),
+                ("special-asset", ExpiringIssue::NoPublicWindow, None),
+            ]
+        );
+
+        assert_eq!(report.entries[0].season, Some("Season 2".to_string()));
+        assert_eq!(report.entries[2].season_id, None);
+        assert_eq!(report.groups().len(), 2);
+
+        match ExpiringReport::from_crawl(&show, &assets, instant("2020-03-01T00:00:00Z"), i64::MAX)
+        {
+            Err(MMCError::InvalidPeriod(_)) => (),
+            err => panic!("Expected InvalidPeriod error but recieved {:?}", err),
+        }
+    }
+
+    fn changelog_entry(resource_type: &str, id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "type": resource_type,
+            "attributes": {
+                "action": "update",
+                "timestamp": "2020-03-01T00:00:00Z",
+                "updated_fields": ["availabilities"]
+            },
+            "links": {
+                "self": format!("https://app.mediamanager.io/api/v1/{}s/{}", resource_type, id)
+            }
+        })
+    }
+
+    #[test]
+    fn expiring_report_refreshes_changed_assets() {
+        let show = random_id();
+        let asset = random_id();
+
+        let m1 = mock_single("shows", show.as_str(), None)
+            .with_status(200)
+            .with_body(
+                serde_json::json!({"data": {"id": show, "attributes": {"title": "Show"}}})
+                    .to_string(),
+            )
+            .create();
+
+        let mocks = [
+            mock_children(
+                "shows",
+                show.as_str(),
+                "assets",
+                serde_json::json!([report_asset(
+                    asset.as_str(),
+                    Some("2020-06-01T00:00:00Z"),
+                    None
+                )]),
+            ),
+            mock_children("shows", show.as_str(), "seasons", serde_json::json!([])),
+            mock_children("shows", show.as_str(), "specials", serde_json::json!([])),
+        ];
+
+        let m2 = mock(
+            "GET",
+            Matcher::Regex(r"^/changelog/\?type=asset&action=update&since=.*page=1".to_string()),
+        )
+        .with_status(200)
+        .with_body(
+            serde_json::json!({
+                "data": [
+                    changelog_entry("asset", asset.as_str()),
+                    changelog_entry("season", asset.as_str())
+                ],
+                "links": {}
+            })
+            .to_string(),
+        )
+        .create();
+
+        let m3 = mock_single("assets", asset.as_str(), None)
+            .expect(1)
+            .with_status(200)
+            .with_body(
+                serde_json::json!({
+                    "data": report_asset(asset.as_str(), Some("2020-03-05T00:00:00Z"), None)
+                })
+                .to_string(),
+            )
+            .create();
+
+        let missing = random_id();
+        let m4 = mock_single("shows", missing.as_str(), None)
+            .with_status(404)
+            .create();
+
+        let report = sample_client()
+            .expiring_report(
+                &[show.as_str(), missing.as_str()],
+                14,
+                instant("2020-03-01T00:00:00Z"),
+            )
+            .unwrap();
+
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].show, Some("Show".to_string()));
+        assert_eq!(
+            report.entries[0].ends,
+            Some(instant("2020-03-05T00:00:00Z"))
+        );
+
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].show_id, missing);
+
+        for m in mocks.iter().chain([m1, m2, m3, m4].iter()) {
+            m.assert();
+        }
+    }
+
+    #[test]
+    fn expiring_report_keeps_crawled_windows_when_refreshing_fails() {
+        let show = random_id();
+        let asset = random_id();
+
+        let m1 = mock_single("shows", show.as_str(), None)
+            .with_status(200)
+            .with_body(
+                serde_json::json!({"data": {"id": show, "attributes": {"title": "Show"}}})
+                    .to_string(),
+            )
+            .create();
+
+        let mocks = [
+            mock_children(
+                "shows",
+                show.as_str(),
+                "assets",
+                serde_json::json!([report_asset(
+                    asset.as_str(),
+                    Some("2020-03-10T00:00:00Z"),
+                    None
+                )]),
+            ),
+            mock_children("shows", show.as_str(), "seasons", serde_json::json!([])),
+            mock_children("shows", show.as_str(), "specials", serde_json::json!([])),
+        ];
+
+        let m2 = mock(
+            "GET",
+            Matcher::Regex(r"^/changelog/\?type=asset&action=update&since=.*page=1".to_string()),
+        )
+        .with_status(200)
+        .with_body(
+            serde_json::json!({
+                "data": [changelog_entry("asset", asset.as_str())],
+                "links": {}
+            })
+            .to_string(),
+        )
+        .create();
+
+        let m3 = mock_single("assets", asset.as_str(), None)
+            .with_status(500)
+            .create();
+
+        let report = sample_client()
+            .expiring_report(&[show.as_str()], 14, instant("2020-03-01T00:00:00Z"))
+            .unwrap();
+
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(
+            report.entries[0].ends,
+            Some(instant("2020-03-10T00:00:00Z"))
+        );
+
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].show_id, show);
+        assert!(report.failures[0].error.contains(asset.as_str()));
+
+        for m in mocks.iter().chain([m1, m2, m3].iter()) {
+            m.assert();
+        }
+    }
 }
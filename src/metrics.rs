@@ -0,0 +1,170 @@
+extern crate reqwest;
+
+use reqwest::Method;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::client::Endpoints;
+use crate::error::MMCError;
+
+/// Upper bounds, in milliseconds, of the latency histogram buckets kept by
+/// [InMemoryMetrics](struct.InMemoryMetrics.html). Latencies above the last bound are counted in
+/// a final overflow bucket.
+pub const LATENCY_BUCKETS_MS: [u64; 10] = [10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Describes a single completed request made by a [Client](struct.Client.html)
+#[derive(Debug)]
+pub struct RequestMetric<'a> {
+    /// The endpoint that was requested, if the request was made against a known endpoint
+    pub endpoint: Option<&'a Endpoints>,
+
+    /// The HTTP method of the request
+    pub method: &'a Method,
+
+    /// The time taken to complete the request
+    pub latency: Duration,
+
+    /// The error the request resulted in, if it failed
+    pub error: Option<&'a MMCError>,
+}
+
+/// A destination for the metrics generated by a [Client](struct.Client.html)
+pub trait MetricsSink: Send + Sync {
+    /// Called once for every request made by the client
+    fn record_request(&self, metric: &RequestMetric);
+
+    /// Called each time a request is sent again by a client configured with
+    /// [with_retries](struct.Client.html#method.with_retries). The request's final outcome is
+    /// still reported once to `record_request`.
+    fn record_retry(&self, _endpoint: Option<&Endpoints>, _method: &Method) {}
+}
+
+impl fmt::Debug for dyn MetricsSink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MetricsSink")
+    }
+}
+
+/// The key that metrics are grouped by in a [MetricsSnapshot](type.MetricsSnapshot.html)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MetricKey {
+    /// The endpoint requested, or `None` for requests made against arbitrary urls
+    pub endpoint: Option<Endpoints>,
+
+    /// The HTTP method of the request
+    pub method: Method,
+}
+
+/// A latency histogram with buckets defined by [LATENCY_BUCKETS_MS](constant.LATENCY_BUCKETS_MS.html)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Histogram {
+    /// Count of observations in each bucket. The final entry counts observations larger than the
+    /// largest bucket bound.
+    pub buckets: Vec<u64>,
+
+    /// Total number of observations
+    pub count: u64,
+
+    /// Sum of all observations in milliseconds
+    pub sum_ms: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, latency: Duration) {
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; LATENCY_BUCKETS_MS.len() + 1];
+        }
+
+        let ms = latency.as_millis() as u64;
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| ms <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum_ms += ms;
+    }
+}
+
+/// Metrics collected for a single endpoint and method pair
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EndpointMetrics {
+    /// Total number of requests made
+    pub requests: u64,
+
+    /// Number of failed requests keyed by the [MMCError](enum.MMCError.html) variant name
+    pub errors: HashMap<&'static str, u64>,
+
+    /// Number of retried requests
+    pub retries: u64,
+
+    /// Latency of all requests
+    pub latency: Histogram,
+}
+
+/// A point in time copy of the metrics held by [InMemoryMetrics](struct.InMemoryMetrics.html)
+pub type MetricsSnapshot = HashMap<MetricKey, EndpointMetrics>;
+
+/// A [MetricsSink](trait.MetricsSink.html) that aggregates metrics in process
+///
+/// Aggregated metrics can be read at any time via `snapshot` and exported in whatever form is
+/// needed.
+#[derive(Debug, Default)]
+pub struct InMemoryMetrics {
+    metrics: Mutex<MetricsSnapshot>,
+}
+
+impl InMemoryMetrics {
+    /// Creates an empty metrics store
+    pub fn new() -> InMemoryMetrics {
+        InMemoryMetrics::default()
+    }
+
+    /// Returns a copy of the metrics collected so far
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.metrics
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn update<F: FnOnce(&mut EndpointMetrics)>(
+        &self,
+        endpoint: Option<&Endpoints>,
+        method: &Method,
+        f: F,
+    ) {
+        let key = MetricKey {
+            endpoint: endpoint.cloned(),
+            method: method.clone(),
+        };
+
+        let mut metrics = self
+            .metrics
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        f(metrics.entry(key).or_default())
+    }
+}
+
+impl MetricsSink for InMemoryMetrics {
+    fn record_request(&self, metric: &RequestMetric) {
+        self.update(metric.endpoint, metric.method, |entry| {
+            entry.requests += 1;
+            entry.latency.observe(metric.latency);
+
+            if let Some(err) = metric.error {
+                *entry.errors.entry(err.kind()).or_insert(0) += 1;
+            }
+        })
+    }
+
+    fn record_retry(&self, endpoint: Option<&Endpoints>, method: &Method) {
+        self.update(endpoint, method, |entry| entry.retries += 1)
+    }
+}
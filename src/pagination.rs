@@ -0,0 +1,88 @@
+use serde_json::Value;
+
+use crate::client::{Client, Endpoints, Params};
+use crate::error::{MMCError, MMCResult};
+
+impl Client {
+    /// Attempts to fetch every page of a list of objects from the requested Media Manager API
+    /// endpoint, returning the combined `data` entries of all pages. Any `page` parameter is
+    /// ignored, as every page is fetched starting from the first.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(endpoint = %endpoint)))]
+    pub fn list_all(&self, endpoint: Endpoints, params: Params) -> MMCResult<Vec<Value>> {
+        Client::collect_pages(params, |page_params| {
+            self.list(endpoint.clone(), page_params)
+        })
+    }
+
+    /// Attempts to fetch every page of a list of child objects belonging to the requested parent
+    /// object, returning the combined `data` entries of all pages. Any `page` parameter is
+    /// ignored.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(endpoint = %endpoint, parent = %parent_endpoint, parent_id = parent_id)
+        )
+    )]
+    pub fn child_list_all(
+        &self,
+        endpoint: Endpoints,
+        parent_id: &str,
+        parent_endpoint: Endpoints,
+        params: Option<Params>,
+    ) -> MMCResult<Vec<Value>> {
+        Client::collect_pages(params.unwrap_or_default(), |page_params| {
+            self.child_list(
+                endpoint.clone(),
+                parent_id,
+                parent_endpoint.clone(),
+                Some(page_params),
+            )
+        })
+    }
+
+    /// Attempts to fetch every page of the changelog matching the given filters, returning the
+    /// combined change entries of all pages. Any `page` parameter is ignored.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn changelog_all(&self, params: Params) -> MMCResult<Vec<Value>> {
+        Client::collect_pages(params, |page_params| self.changelog(page_params))
+    }
+
+    // Requests pages starting from the first until the response no longer links to a next page.
+    // A supplied page is dropped so that it does not conflict with the page being requested.
+    fn collect_pages<F>(params: Params, fetch: F) -> MMCResult<Vec<Value>>
+    where
+        F: Fn(Params) -> MMCResult<String>,
+    {
+        let params: Params = params
+            .into_iter()
+            .filter(|(name, _)| *name != "page")
+            .collect();
+        let mut items = vec![];
+        let mut page = 1;
+
+        loop {
+            let page_string = page.to_string();
+            let mut page_params = params.clone();
+            page_params.push(("page", page_string.as_str()));
+
+            let body = fetch(page_params)?;
+            let parsed: Value = serde_json::from_str(body.as_str()).map_err(MMCError::Parse)?;
+
+            if let Some(data) = parsed.get("data").and_then(Value::as_array) {
+                items.extend(data.iter().cloned());
+            }
+
+            if Client::next_page(&parsed).is_none() {
+                return Ok(items);
+            }
+
+            page += 1;
+        }
+    }
+
+    /// Returns the url of the next page linked to by a list response, if there is one
+    pub fn next_page(response: &Value) -> Option<&str> {
+        response.pointer("/links/next").and_then(Value::as_str)
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+
+use std::fmt;
+
+use crate::asset::Asset;
+use crate::availability::Audience;
+
+/// Why an [Asset](struct.Asset.html) can not be played by a viewer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Unplayable {
+    /// The asset has not been published
+    Unpublished,
+
+    /// The asset is restricted to countries that do not include the viewer's
+    GeoRestricted(String),
+
+    /// The asset has no window for any audience the viewer belongs to
+    NoWindow(Audience),
+
+    /// The viewer's earliest window starts at the contained instant
+    NotYetAvailable(DateTime<Utc>),
+
+    /// The viewer's latest window ended at the contained instant
+    Expired(DateTime<Utc>),
+}
+
+impl fmt::Display for Unplayable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Unplayable::Unpublished => write!(f, "The asset is not published"),
+            Unplayable::GeoRestricted(ref country) => {
+                write!(f, "The asset can not be played in {}", country)
+            }
+            Unplayable::NoWindow(ref audience) => write!(
+                f,
+                "The asset is not available to {} viewers",
+                audience.as_str()
+            ),
+            Unplayable::NotYetAvailable(ref start) => {
+                write!(f, "The asset is not available until {}", start.to_rfc3339())
+            }
+            Unplayable::Expired(ref end) => {
+                write!(
+                    f,
+                    "The asset stopped being available at {}",
+                    end.to_rfc3339()
+                )
+            }
+        }
+    }
+}
+
+/// Whether an [Asset](struct.Asset.html) can be played by a viewer, as determined by
+/// [playability](fn.playability.html)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Playability {
+    /// The asset can be played
+    Playable,
+
+    /// The asset can not be played for the contained reason
+    Unplayable(Unplayable),
+}
+
+impl Playability {
+    /// Returns true when the asset can be played
+    pub fn is_playable(&self) -> bool {
+        matches!(*self, Playability::Playable)
+    }
+}
+
+// The audiences whose windows a viewer may watch during. Members may watch anything available to
+// the public, and station members may also watch anything available to all members.
+fn eligible(viewer: Audience) -> Vec<Audience> {
+    match viewer {
+        Audience::Public => vec![Audience::Public],
+        Audience::AllMembers => vec![Audience::Public, Audience::AllMembers],
+        Audience::StationMembers => Audience::all(),
+    }
+}
+
+/// Determines whether an asset can be played at the supplied instant by a viewer belonging to
+/// an audience, in the country with the supplied ISO 3166 alpha-2 code. No API calls are made.
+///
+/// The asset must be published and, if it has a geo profile, allow the viewer's country. It
+/// must then have a window containing the instant for the viewer's audience or a wider one.
+/// When no window contains the instant, the reason given is the earliest upcoming start if
+/// there is one, and otherwise the latest past end.
+pub fn playability(
+    asset: &Asset,
+    viewer: Audience,
+    country: &str,
+    at: DateTime<Utc>,
+) -> Playability {
+    if !asset.published {
+        return Playability::Unplayable(Unplayable::Unpublished);
+    }
+
+    if let Some(ref profile) = asset.geo_profile {
+        if !profile.allows(country) {
+            return Playability::Unplayable(Unplayable::GeoRestricted(country.to_string()));
+        }
+    }
+
+    let windows: Vec<_> = eligible(viewer)
+        .iter()
+        .filter_map(|audience| asset.availabilities.get(audience))
+        .collect();
+
+    if windows.iter().any(|window| window.contains(at)) {
+        return Playability::Playable;
+    }
+
+    let upcoming = windows
+        .iter()
+        .filter_map(|window| window.start)
+        .filter(|start| *start > at)
+        .min();
+
+    let ended = windows
+        .iter()
+        .filter_map(|window| window.end)
+        .filter(|end| *end <= at)
+        .max();
+
+    let reason = match (upcoming, ended) {
+        (Some(start), _) => Unplayable::NotYetAvailable(start),
+        (None, Some(end)) => Unplayable::Expired(end),
+        (None, None) => Unplayable::NoWindow(viewer),
+    };
+
+    Playability::Unplayable(reason)
+}
@@ -0,0 +1,374 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::asset::Asset;
+use crate::availability::Audience;
+use crate::client::{Client, Endpoints};
+use crate::diff::attributes_of;
+use crate::error::{MMCError, MMCResult};
+use crate::hierarchy::CrawledAsset;
+
+/// Why an asset appears in an [ExpiringReport](struct.ExpiringReport.html)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpiringIssue {
+    /// A window ends within the period covered by the report
+    Expiring,
+
+    /// The asset has no public window at all
+    NoPublicWindow,
+}
+
+/// An asset listed in an [ExpiringReport](struct.ExpiringReport.html), along with the window
+/// that caused it to be listed
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExpiringEntry {
+    /// The id of the show the asset belongs to
+    pub show_id: String,
+
+    /// The title of the show the asset belongs to
+    pub show: Option<String>,
+
+    /// The id of the season the asset belongs to, or `None` for assets of the show itself or of
+    /// its specials
+    pub season_id: Option<String>,
+
+    /// The title of the season the asset belongs to, or its ordinal when it has no title
+    pub season: Option<String>,
+
+    /// The ordinal of the season the asset belongs to
+    pub season_ordinal: Option<u64>,
+
+    /// The id of the asset
+    pub asset_id: String,
+
+    /// The title of the asset
+    pub asset: Option<String>,
+
+    /// Why the asset is listed
+    pub issue: ExpiringIssue,
+
+    /// The audience whose window is ending, for expiring windows
+    pub audience: Option<Audience>,
+
+    /// When the window ends, for expiring windows
+    pub ends: Option<DateTime<Utc>>,
+}
+
+/// A show that could not be fully covered by an [ExpiringReport](struct.ExpiringReport.html)
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExpiringFailure {
+    /// The id of the show
+    pub show_id: String,
+
+    /// Why the show could not be fully covered
+    pub error: String,
+}
+
+/// Assets whose public or member windows end within a number of days, along with assets that
+/// have no public window at all
+///
+/// Entries are ordered by show, then by season, with each show's specials and show level assets
+/// after its seasons, and finally by when their window ends.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExpiringReport {
+    /// The instant the report was generated for
+    pub from: DateTime<Utc>,
+
+    /// The end of the period covered by the report
+    pub until: DateTime<Utc>,
+
+    /// The listed assets
+    pub entries: Vec<ExpiringEntry>,
+
+    /// The shows that could not be fully covered. Shows whose assets could not be fetched are
+    /// missing from the entries, while shows whose changed assets could not be fetched again are
+    /// listed with the windows found by the crawl.
+    pub failures: Vec<ExpiringFailure>,
+}
+
+// Built from milliseconds, as a number of days too large for a Duration would panic
+fn period_end(from: DateTime<Utc>, days: i64) -> MMCResult<DateTime<Utc>> {
+    days.checked_mul(24 * 60 * 60 * 1000)
+        .and_then(|ms| from.checked_add_signed(Duration::milliseconds(ms)))
+        .ok_or_else(|| {
+            MMCError::InvalidPeriod(format!(
+                "A period of {} days from {} ends after the latest date that can be represented",
+                days,
+                from.to_rfc3339()
+            ))
+        })
+}
+
+fn id_of(value: &Value) -> Option<String> {
+    value.get("id").and_then(Value::as_str).map(String::from)
+}
+
+fn attribute<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+    attributes_of(value)
+        .ok()
+        .and_then(|attributes| attributes.get(name))
+}
+
+// The id of the asset a changelog entry describes, or None when it describes another type of
+// resource. Entries give the type and id of the changed resource at the top level, alongside the
+// action and timestamp in their attributes.
+fn changed_asset_id(change: &Value) -> Option<&str> {
+    match change.get("type").and_then(Value::as_str) {
+        Some("asset") => change.get("id").and_then(Value::as_str),
+        _ => None,
+    }
+}
+
+fn title_of(value: &Value) -> Option<String> {
+    attribute(value, "title")
+        .and_then(Value::as_str)
+        .map(String::from)
+}
+
+impl ExpiringReport {
+    /// Builds a report from the assets crawled beneath a show, using their windows as they
+    /// appeared in the crawl. Only windows ending after `from` and no later than `days` days
+    /// after it are listed.
+    pub fn from_crawl(
+        show: &Value,
+        assets: &[CrawledAsset],
+        from: DateTime<Utc>,
+        days: i64,
+    ) -> MMCResult<ExpiringReport> {
+        let mut report = ExpiringReport {
+            from,
+            until: period_end(from, days)?,
+            entries: vec![],
+            failures: vec![],
+        };
+
+        report.add_show(show, assets)?;
+        report.sort();
+
+        Ok(report)
+    }
+
+    /// Combines the entries and failures of several reports covering the same period
+    pub fn merge(mut self, other: ExpiringReport) -> ExpiringReport {
+        self.entries.extend(other.entries);
+        self.failures.extend(other.failures);
+        self.sort();
+        self
+    }
+
+    /// Returns the entries grouped by show and season, in report order
+    pub fn groups(&self) -> Vec<&[ExpiringEntry]> {
+        let mut groups = vec![];
+        let mut start = 0;
+
+        for i in 1..=self.entries.len() {
+            let ends_group = i == self.entries.len()
+                || self.entries[i].show_id != self.entries[start].show_id
+                || self.entries[i].season_id != self.entries[start].season_id;
+
+            if ends_group {
+                groups.push(&self.entries[start..i]);
+                start = i;
+            }
+        }
+
+        groups
+    }
+
+    fn add_show(&mut self, show: &Value, assets: &[CrawledAsset]) -> MMCResult<()> {
+        let show_id = id_of(show).unwrap_or_default();
+        let show_title = title_of(show);
+
+        for crawled in assets {
+            let asset = Asset::from_value(&crawled.asset)?;
+            let season = crawled.ancestor(&Endpoints::Season);
+            let season_ordinal = season
+                .and_then(|season| attribute(season, "ordinal"))
+                .and_then(Value::as_u64);
+
+            let entry = |issue, audience, ends| ExpiringEntry {
+                show_id: show_id.clone(),
+                show: show_title.clone(),
+                season_id: season.and_then(id_of),
+                season: season
+                    .and_then(title_of)
+                    .or_else(|| season_ordinal.map(|ordinal| format!("Season {}", ordinal))),
+                season_ordinal,
+                asset_id: asset.id.clone(),
+                asset: asset.title.clone(),
+                issue,
+                audience,
+                ends,
+            };
+
+            if !asset.availabilities.contains_key(&Audience::Public) {
+                self.entries
+                    .push(entry(ExpiringIssue::NoPublicWindow, None, None));
+            }
+
+            for (audience, window) in asset.availabilities.iter() {
+                if let Some(end) = window.end {
+                    if end > self.from && end <= self.until {
+                        self.entries.push(entry(
+                            ExpiringIssue::Expiring,
+                            Some(*audience),
+                            Some(end),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn sort(&mut self) {
+        self.entries.sort_by(|a, b| {
+            (
+                &a.show,
+                &a.show_id,
+                a.season_id.is_none(),
+                a.season_ordinal,
+                &a.season_id,
+            )
+                .cmp(&(
+                    &b.show,
+                    &b.show_id,
+                    b.season_id.is_none(),
+                    b.season_ordinal,
+                    &b.season_id,
+                ))
+                .then_with(|| a.ends.is_none().cmp(&b.ends.is_none()))
+                .then_with(|| a.ends.cmp(&b.ends))
+        });
+    }
+}
+
+impl Client {
+    /// Attempts to build an [ExpiringReport](struct.ExpiringReport.html) covering the shows with
+    /// the supplied ids, or every show when none are supplied. The assets of each show are found
+    /// with [crawl_assets](struct.Client.html#method.crawl_assets), and any asset changed in the
+    /// changelog since the crawl of its show began is fetched again so that the report reflects
+    /// its latest windows. Shows that can not be fetched or crawled are listed in the report's
+    /// failures rather than failing the whole report, as are shows whose changed assets can not
+    /// be fetched again, which are reported from the crawl instead.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(days = days)))]
+    pub fn expiring_report(
+        &self,
+        show_ids: &[&str],
+        days: i64,
+        from: DateTime<Utc>,
+    ) -> MMCResult<ExpiringReport> {
+        let shows: Vec<(String, MMCResult<Value>)> = if show_ids.is_empty() {
+            self.list_all(Endpoints::Show, vec![])?
+                .into_iter()
+                .map(|show| (id_of(&show).unwrap_or_default(), Ok(show)))
+                .collect()
+        } else {
+            show_ids
+                .iter()
+                .map(|id| {
+                    let show = self.show(id, None).and_then(|body| {
+                        let parsed: Value =
+                            serde_json::from_str(body.as_str()).map_err(MMCError::Parse)?;
+
+                        Ok(parsed.get("data").cloned().unwrap_or(parsed))
+                    });
+
+                    (id.to_string(), show)
+                })
+                .collect()
+        };
+
+        let mut report = ExpiringReport {
+            from,
+            until: period_end(from, days)?,
+            entries: vec![],
+            failures: vec![],
+        };
+
+        for (id, show) in shows {
+            match show.and_then(|show| self.expiring_show(&show, id.as_str(), from, days)) {
+                Ok(covered) => report = report.merge(covered),
+                Err(err) => report.failures.push(ExpiringFailure {
+                    show_id: id,
+                    error: err.to_string(),
+                }),
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn expiring_show(
+        &self,
+        show: &Value,
+        id: &str,
+        from: DateTime<Utc>,
+        days: i64,
+    ) -> MMCResult<ExpiringReport> {
+        let started = Utc::now();
+        let mut assets = self.crawl_assets(Endpoints::Show, id)?;
+        let stale = self.refresh_changed(&mut assets, started);
+
+        let mut report = ExpiringReport::from_crawl(show, &assets, from, days)?;
+        report
+            .failures
+            .extend(stale.into_iter().map(|error| ExpiringFailure {
+                show_id: id.to_string(),
+                error,
+            }));
+
+        Ok(report)
+    }
+
+    // Replaces crawled assets that the changelog reports as updated since the crawl started, as
+    // the pages listing them may have been fetched before the change was made. Assets that can
+    // not be fetched again keep their crawled state, and a description of each failure is
+    // returned.
+    fn refresh_changed(&self, assets: &mut [CrawledAsset], since: DateTime<Utc>) -> Vec<String> {
+        let since = since.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let changes = match self.changelog_all(vec![
+            ("type", "asset"),
+            ("action", "update"),
+            ("since", since.as_str()),
+        ]) {
+            Ok(changes) => changes,
+            Err(err) => {
+                return vec![format!(
+                    "Unable to check for assets changed while crawling, so windows are as \
+                     crawled. {}",
+                    err
+                )]
+            }
+        };
+
+        let mut errors = vec![];
+
+        for changed_id in changes.iter().filter_map(changed_asset_id) {
+            let crawled = match assets
+                .iter_mut()
+                .find(|crawled| id_of(&crawled.asset).as_deref() == Some(changed_id))
+            {
+                Some(crawled) => crawled,
+                None => continue,
+            };
+
+            let refreshed = self.asset(changed_id, None).and_then(|body| {
+                serde_json::from_str::<Value>(body.as_str()).map_err(MMCError::Parse)
+            });
+
+            match refreshed {
+                Ok(parsed) => crawled.asset = parsed.get("data").cloned().unwrap_or(parsed),
+                Err(err) => errors.push(format!(
+                    "Unable to fetch the changed asset {}, so its windows are as crawled. {}",
+                    changed_id, err
+                )),
+            }
+        }
+
+        errors
+    }
+}